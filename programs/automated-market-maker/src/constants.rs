@@ -3,3 +3,80 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const CONFIG_SEED: &[u8] = b"config";
 pub const LP_SEED: &[u8] = b"lp";
+#[constant]
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+#[constant]
+pub const FACTORY_SEED: &[u8] = b"factory";
+#[constant]
+pub const FARM_SEED: &[u8] = b"farm";
+#[constant]
+pub const STAKE_SEED: &[u8] = b"stake";
+#[constant]
+pub const LOCK_SEED: &[u8] = b"lock";
+#[constant]
+pub const ORDER_SEED: &[u8] = b"order";
+#[constant]
+pub const PAIR_SEED: &[u8] = b"pair";
+#[constant]
+pub const POSITION_SEED: &[u8] = b"position";
+#[constant]
+pub const SWAP_RECORD_SEED: &[u8] = b"swap_record";
+#[constant]
+pub const TWAMM_SEED: &[u8] = b"twamm";
+#[constant]
+pub const LONG_TERM_ORDER_SEED: &[u8] = b"long_term_order";
+#[constant]
+pub const TWAMM_EXPIRY_SEED: &[u8] = b"twamm_expiry";
+#[constant]
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
+/// Legacy `locked` flag, pauses every operation.
+#[constant]
+pub const PAUSE_ALL: u8 = 1;
+#[constant]
+pub const PAUSE_SWAP: u8 = 2;
+#[constant]
+pub const PAUSE_DEPOSIT: u8 = 4;
+#[constant]
+pub const PAUSE_WITHDRAW: u8 = 8;
+#[constant]
+pub const PAUSE_FLASH: u8 = 16;
+#[constant]
+pub const PAUSE_COLLECT: u8 = 32;
+pub const PAUSE_MASK: u8 =
+    PAUSE_ALL | PAUSE_SWAP | PAUSE_DEPOSIT | PAUSE_WITHDRAW | PAUSE_FLASH | PAUSE_COLLECT;
+/// Current `Config` layout version, bumped whenever the account size changes.
+/// Fields carved out of the reserved bytes start zeroed and need no migration.
+#[constant]
+pub const CONFIG_VERSION: u8 = 2;
+/// Bytes kept free at the end of `Config` for future fields.
+pub const CONFIG_RESERVED: usize = 80;
+/// Sandwich guard off, the default.
+#[constant]
+pub const SANDWICH_GUARD_OFF: u8 = 0;
+/// Charge `sandwich_fee` on top of the swap fee when a signer reverses its
+/// swap direction within a slot.
+#[constant]
+pub const SANDWICH_GUARD_SURCHARGE: u8 = 1;
+/// Reject a signer's opposite-direction swap within a slot.
+#[constant]
+pub const SANDWICH_GUARD_REJECT: u8 = 2;
+pub const MAX_FEE_BPS: u16 = 10_000;
+/// Largest share of the swap fee a referrer can receive, in basis points of
+/// the fee, so LPs always keep at least half.
+#[constant]
+pub const MAX_REFERRAL_FEE: u16 = 5_000;
+pub const MAX_FEE_TIERS: usize = 16;
+pub const MAX_FARM_REWARDS: usize = 8;
+pub const MAX_DISCOUNT_TIERS: usize = 4;
+/// Long-term orders expire on multiples of this many seconds, which bounds
+/// the number of distinct expiries a TWAMM has to track.
+#[constant]
+pub const TWAMM_INTERVAL: i64 = 3_600;
+pub const MAX_TWAMM_EXPIRIES: usize = 32;
+/// Seconds for the dynamic fee volatility accumulator to halve.
+pub const VOLATILITY_HALF_LIFE: u64 = 60;
+/// Divisor applied to the volatility accumulator before adding it to the base fee.
+pub const VOLATILITY_FEE_DIVISOR: u64 = 4;
+/// `sha256("global:flash_swap_callback")[..8]`, so Anchor programs can handle
+/// the callback as a regular `flash_swap_callback` instruction.
+pub const FLASH_SWAP_CALLBACK_DISCRIMINATOR: [u8; 8] = [225, 54, 80, 1, 45, 208, 202, 124];
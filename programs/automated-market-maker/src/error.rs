@@ -4,12 +4,102 @@ use anchor_lang::prelude::*;
 pub enum AMMError {
     #[msg("Invalid config authority")]
     InvalidConfigAuthority,
-    #[msg("Pool is locked")]
-    PoolLocked,
+    #[msg("Operation is paused")]
+    OperationPaused,
     #[msg("Amount must be greater than 0")]
     InvalidAmount,
     #[msg("Minimum amount of both tokens cannot be 0")]
     InvalidMinAmount,
     #[msg("Slippage exceeded")]
     SlippageExceeded,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Insufficient liquidity in pool")]
+    InsufficientLiquidity,
+    #[msg("Invalid swap route")]
+    InvalidRoute,
+    #[msg("Fee must not exceed 10000 basis points")]
+    InvalidFee,
+    #[msg("Flash loan is active")]
+    FlashLoanActive,
+    #[msg("No flash loan to repay")]
+    NoFlashLoan,
+    #[msg("Flash borrow must be followed by a flash repay")]
+    MissingFlashRepay,
+    #[msg("Flash borrow cannot be invoked via CPI")]
+    FlashBorrowCpi,
+    #[msg("Protocol fee account required")]
+    MissingProtocolAccount,
+    #[msg("Callback program cannot be this program")]
+    InvalidCallbackProgram,
+    #[msg("Constant product invariant violated")]
+    InvariantViolated,
+    #[msg("Mint X must sort before mint Y")]
+    InvalidMintOrder,
+    #[msg("Invalid program config admin")]
+    InvalidProgramAdmin,
+    #[msg("Fee tier is not enabled")]
+    InvalidFeeTier,
+    #[msg("Maximum number of fee tiers reached")]
+    FeeTiersFull,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Invalid config guardian")]
+    InvalidConfigGuardian,
+    #[msg("Config is not a migratable version")]
+    InvalidConfigVersion,
+    #[msg("Pool still holds liquidity")]
+    PoolNotEmpty,
+    #[msg("Recipient token account missing")]
+    MissingRecipientAccount,
+    #[msg("Position is not owned by the user")]
+    InvalidPositionOwner,
+    #[msg("Invalid farm reward schedule")]
+    InvalidFarmSchedule,
+    #[msg("Insufficient staked balance")]
+    InsufficientStake,
+    #[msg("Farm has no free reward slot")]
+    FarmRewardsFull,
+    #[msg("Invalid reward accounts")]
+    InvalidRewardAccounts,
+    #[msg("Unlock time must be in the future")]
+    InvalidUnlockTime,
+    #[msg("LP tokens are still locked")]
+    LockActive,
+    #[msg("Invalid order accounts")]
+    InvalidOrderAccounts,
+    #[msg("Invalid long-term order expiry")]
+    InvalidOrderExpiry,
+    #[msg("TWAMM has no free expiry slot")]
+    TwammExpiriesFull,
+    #[msg("TWAMM accounts missing")]
+    MissingTwammAccounts,
+    #[msg("Price impact limit exceeded")]
+    PriceImpactExceeded,
+    #[msg("Invalid sandwich guard mode")]
+    InvalidSandwichGuard,
+    #[msg("Missing or invalid swap record")]
+    InvalidSwapRecord,
+    #[msg("Opposite swap by the same signer in this slot")]
+    SandwichDetected,
+    #[msg("Signer is not on the pool allowlist")]
+    NotAllowlisted,
+    #[msg("Invalid fee discount tiers")]
+    InvalidDiscountTiers,
+    #[msg("Invalid fee discount token account")]
+    InvalidDiscountAccount,
+    #[msg("Referrer account must hold the input mint")]
+    InvalidReferrerAccount,
+    #[msg("Fee tier of a registered pool cannot change")]
+    FeeTierLocked,
+    #[msg("Missing pair registry account")]
+    MissingPairAccount,
+    #[msg("Pool has open orders")]
+    OpenOrders,
+    #[msg("Missing archived expiry account")]
+    MissingArchivedExpiry,
+    #[msg("Invalid archived expiry account")]
+    InvalidArchivedExpiry,
+    #[msg("Reward mint already has a stream")]
+    DuplicateRewardMint,
 }
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Emitted by every swap, once per hop for routed swaps.
+#[event]
+pub struct SwapEvent {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub is_x: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Fee charged in the input mint, including the referral share.
+    pub fee: u64,
+    /// Token account paid `referral_fee`, if any.
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Config, Farm, RewardStream, FARM_SEED, MAX_FARM_REWARDS};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddRewardArgs {
+    pub reward_per_second: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(Accounts)]
+pub struct AddReward<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(has_one = authority @ AMMError::InvalidConfigAuthority)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        has_one = config,
+        seeds = [FARM_SEED, config.key().as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub farm_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = funder,
+        associated_token::token_program = token_program,
+    )]
+    pub funder_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl AddReward<'_> {
+    /// Adds a reward stream approved by the pool authority and funds its
+    /// vault with the full schedule from `funder`.
+    pub fn handler(ctx: Context<AddReward>, args: AddRewardArgs) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let start_time = args.start_time.max(now);
+
+        require_gt!(args.reward_per_second, 0, AMMError::InvalidAmount);
+        require_gt!(args.end_time, start_time, AMMError::InvalidFarmSchedule);
+        require_gt!(
+            MAX_FARM_REWARDS,
+            ctx.accounts.farm.rewards.len(),
+            AMMError::FarmRewardsFull
+        );
+        require!(
+            !ctx.accounts
+                .farm
+                .rewards
+                .iter()
+                .any(|stream| stream.mint == ctx.accounts.reward_mint.key()),
+            AMMError::DuplicateRewardMint
+        );
+
+        let total = args
+            .reward_per_second
+            .checked_mul((args.end_time - start_time) as u64)
+            .ok_or(AMMError::MathOverflow)?;
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.funder.to_account_info(),
+                    from: ctx.accounts.funder_reward.to_account_info(),
+                    to: ctx.accounts.farm_reward.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                },
+            ),
+            total,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+
+        let farm = &mut ctx.accounts.farm;
+        farm.update(now)?;
+        farm.rewards.push(RewardStream {
+            mint: ctx.accounts.reward_mint.key(),
+            funder: ctx.accounts.funder.key(),
+            reward_per_second: args.reward_per_second,
+            start_time,
+            end_time: args.end_time,
+            reward_per_share: 0,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, AllowlistEntry, Config, ALLOWLIST_SEED};
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    #[account(mut)]
+    pub allowlist_authority: Signer<'info>,
+    #[account(has_one = allowlist_authority @ AMMError::InvalidConfigAuthority)]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: Wallet being approved
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = allowlist_authority,
+        space = AllowlistEntry::DISCRIMINATOR.len() + AllowlistEntry::INIT_SPACE,
+        seeds = [ALLOWLIST_SEED, config.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub allowlist_entry: Box<Account<'info, AllowlistEntry>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl AddToAllowlist<'_> {
+    pub fn handler(ctx: Context<AddToAllowlist>) -> Result<()> {
+        ctx.accounts.allowlist_entry.set_inner(AllowlistEntry {
+            config: ctx.accounts.config.key(),
+            user: ctx.accounts.user.key(),
+            bump: ctx.bumps.allowlist_entry,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{ArchivedExpiry, Twamm, TWAMM_EXPIRY_SEED, TWAMM_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ArchiveTwammExpiryArgs {
+    pub end_time: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ArchiveTwammExpiryArgs)]
+pub struct ArchiveTwammExpiry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, twamm.config.as_ref()],
+        bump = twamm.bump,
+    )]
+    pub twamm: Box<Account<'info, Twamm>>,
+    #[account(
+        init,
+        payer = payer,
+        space = ArchivedExpiry::DISCRIMINATOR.len() + ArchivedExpiry::INIT_SPACE,
+        seeds = [
+            TWAMM_EXPIRY_SEED,
+            twamm.key().as_ref(),
+            args.end_time.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub archived_expiry: Box<Account<'info, ArchivedExpiry>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ArchiveTwammExpiry<'_> {
+    /// Moves an executed expiry out of the TWAMM so new orders can use its
+    /// slot. Orders against it then pass the archive to withdraw or cancel.
+    pub fn handler(ctx: Context<ArchiveTwammExpiry>, args: ArchiveTwammExpiryArgs) -> Result<()> {
+        let expiry = ctx.accounts.twamm.archive_expiry(args.end_time)?;
+
+        ctx.accounts.archived_expiry.set_inner(ArchivedExpiry {
+            twamm: ctx.accounts.twamm.key(),
+            end_time: expiry.end_time,
+            earnings_per_rate_x: expiry.earnings_per_rate_x,
+            earnings_per_rate_y: expiry.earnings_per_rate_y,
+            bump: ctx.bumps.archived_expiry,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Config, LimitOrder, CONFIG_SEED, ORDER_SEED};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = config,
+        has_one = owner,
+        seeds = [
+            ORDER_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            order.id.to_le_bytes().as_ref(),
+        ],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = order,
+        associated_token::token_program = token_program,
+    )]
+    pub order_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = order,
+        associated_token::token_program = token_program,
+    )]
+    pub order_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CancelOrder<'_> {
+    /// Returns unfilled input and any proceeds to the owner and closes the
+    /// order. Also used to collect a filled order.
+    pub fn handler(ctx: Context<CancelOrder>) -> Result<()> {
+        let order = &ctx.accounts.order;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            ORDER_SEED,
+            order.config.as_ref(),
+            order.owner.as_ref(),
+            &order.id.to_le_bytes(),
+            &[order.bump],
+        ]];
+
+        for (from, to, mint, decimals) in [
+            (
+                &ctx.accounts.order_x,
+                &ctx.accounts.owner_x,
+                &ctx.accounts.mint_x,
+                ctx.accounts.mint_x.decimals,
+            ),
+            (
+                &ctx.accounts.order_y,
+                &ctx.accounts.owner_y,
+                &ctx.accounts.mint_y,
+                ctx.accounts.mint_y.decimals,
+            ),
+        ] {
+            if from.amount > 0 {
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            authority: order.to_account_info(),
+                            from: from.to_account_info(),
+                            to: to.to_account_info(),
+                            mint: mint.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    from.amount,
+                    decimals,
+                )?;
+            }
+
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: from.to_account_info(),
+                    destination: ctx.accounts.owner.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        ctx.accounts.config.open_orders = ctx.accounts.config.open_orders.saturating_sub(1);
+
+        Ok(())
+    }
+}
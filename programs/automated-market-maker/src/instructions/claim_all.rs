@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Farm, FarmStake, FARM_SEED, STAKE_SEED};
+
+/// Remaining accounts per reward stream: `[reward_mint, farm_reward, user_reward]`.
+pub const CLAIM_REWARD_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.config.as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(
+        mut,
+        has_one = farm,
+        seeds = [STAKE_SEED, farm.key().as_ref(), user.key().as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Box<Account<'info, FarmStake>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimAll<'info> {
+    /// Pays out every stream's owed rewards. Remaining accounts list each
+    /// stream in `Farm::rewards` order; `user_reward` must already exist.
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, ClaimAll<'info>>) -> Result<()> {
+        let farm = &mut ctx.accounts.farm;
+        farm.update(Clock::get()?.unix_timestamp)?;
+        ctx.accounts.stake.settle(farm)?;
+
+        let chunks = ctx.remaining_accounts.chunks_exact(CLAIM_REWARD_ACCOUNTS);
+        require!(
+            chunks.remainder().is_empty() && chunks.len() == farm.rewards.len(),
+            AMMError::InvalidRewardAccounts
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[FARM_SEED, farm.config.as_ref(), &[farm.bump]]];
+        let token_program = ctx.accounts.token_program.key();
+
+        for ((accounts, stream), reward) in chunks
+            .zip(farm.rewards.iter())
+            .zip(ctx.accounts.stake.rewards.iter_mut())
+        {
+            let reward_mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+            let user_reward = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+            require!(
+                reward_mint.key() == stream.mint
+                    && *accounts[0].owner == token_program
+                    && accounts[1].key()
+                        == get_associated_token_address_with_program_id(
+                            &farm.key(),
+                            &stream.mint,
+                            &token_program,
+                        )
+                    && user_reward.mint == stream.mint
+                    && user_reward.owner == ctx.accounts.user.key(),
+                AMMError::InvalidRewardAccounts
+            );
+
+            let amount = reward.owed;
+            reward.owed = 0;
+
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        authority: farm.to_account_info(),
+                        from: accounts[1].clone(),
+                        to: accounts[2].clone(),
+                        mint: accounts[0].clone(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                reward_mint.decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+}
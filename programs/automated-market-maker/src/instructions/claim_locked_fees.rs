@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    error::AMMError, utils::settle_twamm, Config, LpLock, Twamm, CONFIG_SEED, LOCK_SEED, LP_SEED,
+    PAUSE_COLLECT,
+};
+
+#[derive(Accounts)]
+pub struct ClaimLockedFees<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = config,
+        has_one = owner,
+        seeds = [
+            LOCK_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            lock.id.to_le_bytes().as_ref(),
+        ],
+        bump = lock.bump,
+    )]
+    pub lock: Box<Account<'info, LpLock>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lock_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ClaimLockedFees<'_> {
+    /// LP tokens worth the fees earned since the last claim, so that burning
+    /// them leaves the remaining lock with its principal value. Burning LP
+    /// pays out both reserves, so the fees are valued in x at the pool price
+    /// and divided by the x value of both reserves per LP token.
+    fn fee_lp(ctx: &Context<ClaimLockedFees>) -> Result<u64> {
+        let (fee_x, fee_y) = ctx.accounts.lock.earned(&ctx.accounts.config);
+        let vault_x = ctx.accounts.vault_x.amount as u128;
+        let vault_y = ctx.accounts.vault_y.amount as u128;
+        let reserve_y = ctx.accounts.config.reserve_y(ctx.accounts.vault_y.amount)? as u128;
+
+        if vault_x == 0 || reserve_y == 0 {
+            return Ok(0);
+        }
+
+        // round the fees down and the reserves up, in favour of the principal
+        let fee_value = fee_x as u128 + fee_y as u128 * vault_x / reserve_y;
+        let reserves_value = vault_x + (vault_y * vault_x).div_ceil(reserve_y);
+
+        fee_value
+            .checked_mul(ctx.accounts.mint_lp.supply as u128)
+            .map(|v| v / reserves_value)
+            .map(|v| v.min(ctx.accounts.lock.amount as u128) as u64)
+            .ok_or(AMMError::MathOverflow.into())
+    }
+
+    fn transfer_tokens(ctx: &Context<ClaimLockedFees>, is_x: bool, amount: u64) -> Result<()> {
+        let (from, to, mint, decimals) = match is_x {
+            true => (
+                ctx.accounts.vault_x.to_account_info(),
+                ctx.accounts.owner_x.to_account_info(),
+                ctx.accounts.mint_x.to_account_info(),
+                ctx.accounts.mint_x.decimals,
+            ),
+            false => (
+                ctx.accounts.vault_y.to_account_info(),
+                ctx.accounts.owner_y.to_account_info(),
+                ctx.accounts.mint_y.to_account_info(),
+                ctx.accounts.mint_y.decimals,
+            ),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CONFIG_SEED,
+            &ctx.accounts.config.seed.to_le_bytes(),
+            &[ctx.accounts.config.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.config.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+                signer_seeds,
+            ),
+            amount,
+            decimals,
+        )
+    }
+
+    /// Withdraws the lock's share of fee growth by burning the equivalent LP
+    /// tokens from escrow. Principal is never withdrawn.
+    pub fn handler(ctx: Context<ClaimLockedFees>) -> Result<()> {
+        Config::invariant(&ctx.accounts.config, PAUSE_COLLECT)?;
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
+
+        let amount = ClaimLockedFees::fee_lp(&ctx)?;
+
+        // leave the checkpoint untouched so that dust keeps accruing
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let share = |vault: u64| {
+            (vault as u128 * amount as u128 / ctx.accounts.mint_lp.supply as u128) as u64
+        };
+        let amount_x = share(ctx.accounts.vault_x.amount);
+        let amount_y = share(ctx.accounts.vault_y.amount);
+
+        ClaimLockedFees::transfer_tokens(&ctx, true, amount_x)?;
+        ClaimLockedFees::transfer_tokens(&ctx, false, amount_y)?;
+
+        let lock = &ctx.accounts.lock;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            LOCK_SEED,
+            lock.config.as_ref(),
+            lock.owner.as_ref(),
+            &lock.id.to_le_bytes(),
+            &[lock.bump],
+        ]];
+
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    authority: lock.to_account_info(),
+                    from: ctx.accounts.lock_lp.to_account_info(),
+                    mint: ctx.accounts.mint_lp.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let supply = ctx.accounts.mint_lp.supply;
+        ctx.accounts
+            .config
+            .scale_virtual_y(supply, supply - amount)?;
+
+        let lock = &mut ctx.accounts.lock;
+        lock.amount -= amount;
+        lock.fee_growth_x = ctx.accounts.config.fee_growth_x;
+        lock.fee_growth_y = ctx.accounts.config.fee_growth_y;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Farm, FarmStake, FARM_SEED, STAKE_SEED};
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.config.as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub farm_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_reward: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = farm,
+        seeds = [STAKE_SEED, farm.key().as_ref(), user.key().as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Box<Account<'info, FarmStake>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ClaimRewards<'_> {
+    /// Pays out the owed rewards of the stream emitting `reward_mint`.
+    pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+        let farm = &mut ctx.accounts.farm;
+        farm.update(Clock::get()?.unix_timestamp)?;
+
+        let stake = &mut ctx.accounts.stake;
+        stake.settle(farm)?;
+
+        let index = farm
+            .rewards
+            .iter()
+            .position(|stream| stream.mint == ctx.accounts.reward_mint.key())
+            .ok_or(AMMError::InvalidRewardAccounts)?;
+
+        let amount = stake.rewards[index].owed;
+        stake.rewards[index].owed = 0;
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[FARM_SEED, farm.config.as_ref(), &[farm.bump]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: farm.to_account_info(),
+                    from: ctx.accounts.farm_reward.to_account_info(),
+                    to: ctx.accounts.user_reward.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.reward_mint.decimals,
+        )
+    }
+}
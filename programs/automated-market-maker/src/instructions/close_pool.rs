@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+use crate::{error::AMMError, Config, Pair, CONFIG_SEED, LP_SEED, PAIR_SEED};
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ AMMError::InvalidConfigAuthority,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Registry entry, absent for pools migrated from the v0 layout.
+    #[account(
+        mut,
+        close = authority,
+        has_one = config,
+        seeds = [
+            PAIR_SEED,
+            Pair::sorted(mint_x.to_account_info().key, mint_y.to_account_info().key)[0],
+            Pair::sorted(mint_x.to_account_info().key, mint_y.to_account_info().key)[1],
+            pair.fee.to_le_bytes().as_ref(),
+        ],
+        bump = pair.bump,
+    )]
+    pub pair: Option<Box<Account<'info, Pair>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ClosePool<'_> {
+    /// Closes both vaults and the config. The LP mint stays open since the
+    /// token program does not allow closing a mint without an extension.
+    pub fn handler(ctx: Context<ClosePool>) -> Result<()> {
+        require!(
+            ctx.accounts.config.legacy || ctx.accounts.pair.is_some(),
+            AMMError::MissingPairAccount
+        );
+        require_eq!(ctx.accounts.config.open_orders, 0, AMMError::OpenOrders);
+        require_eq!(ctx.accounts.mint_lp.supply, 0, AMMError::PoolNotEmpty);
+        require_eq!(ctx.accounts.vault_x.amount, 0, AMMError::PoolNotEmpty);
+        require_eq!(ctx.accounts.vault_y.amount, 0, AMMError::PoolNotEmpty);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CONFIG_SEED,
+            &ctx.accounts.config.seed.to_le_bytes(),
+            &[ctx.accounts.config.bump],
+        ]];
+
+        for vault in [&ctx.accounts.vault_x, &ctx.accounts.vault_y] {
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: ctx.accounts.authority.to_account_info(),
+                    authority: ctx.accounts.config.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        Ok(())
+    }
+}
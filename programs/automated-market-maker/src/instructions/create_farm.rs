@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::AMMError, Config, Farm, FARM_SEED, LP_SEED};
+
+#[derive(Accounts)]
+pub struct CreateFarm<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(has_one = authority @ AMMError::InvalidConfigAuthority)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        space = Farm::DISCRIMINATOR.len() + Farm::INIT_SPACE,
+        seeds = [FARM_SEED, config.key().as_ref()],
+        bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint_lp,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub farm_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateFarm<'_> {
+    /// Creates the staking pool. Reward streams are added with `add_reward`.
+    pub fn handler(ctx: Context<CreateFarm>) -> Result<()> {
+        ctx.accounts.farm.set_inner(Farm {
+            config: ctx.accounts.config.key(),
+            mint_lp: ctx.accounts.mint_lp.key(),
+            last_update: Clock::get()?.unix_timestamp,
+            total_staked: 0,
+            bump: ctx.bumps.farm,
+            rewards: Vec::new(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::AMMError, Config, Twamm, TWAMM_SEED};
+
+#[derive(Accounts)]
+pub struct CreateTwamm<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority @ AMMError::InvalidConfigAuthority,
+        has_one = mint_x,
+        has_one = mint_y,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        space = Twamm::DISCRIMINATOR.len() + Twamm::INIT_SPACE,
+        seeds = [TWAMM_SEED, config.key().as_ref()],
+        bump,
+    )]
+    pub twamm: Box<Account<'info, Twamm>>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl CreateTwamm<'_> {
+    /// Creates the pool's long-term order book. From then on swaps, deposits
+    /// and withdrawals have to pass the TWAMM accounts.
+    pub fn handler(ctx: Context<CreateTwamm>) -> Result<()> {
+        ctx.accounts.twamm.set_inner(Twamm {
+            config: ctx.accounts.config.key(),
+            last_executed: Clock::get()?.unix_timestamp,
+            sale_rate_x: 0,
+            sale_rate_y: 0,
+            earnings_per_rate_x: 0,
+            earnings_per_rate_y: 0,
+            bump: ctx.bumps.twamm,
+            expiries: Vec::new(),
+        });
+        ctx.accounts.config.twamm = true;
+
+        Ok(())
+    }
+}
@@ -7,13 +7,19 @@ use anchor_spl::{
 };
 use constant_product_curve::ConstantProduct;
 
-use crate::{error::AMMError, Config, CONFIG_SEED, LP_SEED};
+use crate::{
+    error::AMMError,
+    utils::{is_native_mint, settle_twamm, wrap_sol},
+    AllowlistEntry, Config, LpPosition, Twamm, CONFIG_SEED, LP_SEED, PAUSE_DEPOSIT,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DepositArgs {
     amount: u64,
     max_x: u64,
     max_y: u64,
+    /// Pay native SOL from lamports.
+    wrap_sol: bool,
 }
 
 #[derive(Accounts)]
@@ -21,6 +27,7 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
@@ -60,26 +67,68 @@ pub struct Deposit<'info> {
     )]
     pub user_lp: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
         associated_token::mint = mint_x,
         associated_token::authority = user,
         associated_token::token_program = token_program,
     )]
     pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
         associated_token::mint = mint_y,
         associated_token::authority = user,
         associated_token::token_program = token_program,
     )]
     pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Owner of the minted LP tokens, defaults to `user` when absent
+    pub recipient: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_lp,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_lp: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Must belong to whoever receives the LP tokens.
+    #[account(
+        mut,
+        has_one = config,
+        constraint = position.owner
+            == recipient.as_ref().map_or(user.key(), |recipient| recipient.key())
+            @ AMMError::InvalidPositionOwner,
+    )]
+    pub position: Option<Box<Account<'info, LpPosition>>>,
+    #[account(
+        has_one = config,
+        constraint = allowlist_entry.user == user.key() @ AMMError::NotAllowlisted,
+    )]
+    pub allowlist_entry: Option<Box<Account<'info, AllowlistEntry>>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl Deposit<'_> {
-    fn transfer_tokens(ctx: &Context<Deposit>, is_x: bool, amount: u64) -> Result<()> {
+    fn transfer_tokens(ctx: &Context<Deposit>, is_x: bool, amount: u64, wrap: bool) -> Result<()> {
         let (from, to, mint, decimals) = match is_x {
             true => (
                 ctx.accounts.user_x.to_account_info(),
@@ -95,6 +144,16 @@ impl Deposit<'_> {
             ),
         };
 
+        if wrap && is_native_mint(mint.key) {
+            wrap_sol(
+                &ctx.accounts.system_program,
+                &ctx.accounts.token_program,
+                ctx.accounts.user.to_account_info(),
+                from.clone(),
+                amount,
+            )?;
+        }
+
         transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -111,7 +170,21 @@ impl Deposit<'_> {
     }
 
     pub fn handler(ctx: Context<Deposit>, args: DepositArgs) -> Result<()> {
-        Config::invariant(&ctx.accounts.config)?;
+        Config::invariant(&ctx.accounts.config, PAUSE_DEPOSIT)?;
+        require!(
+            !ctx.accounts.config.permissioned || ctx.accounts.allowlist_entry.is_some(),
+            AMMError::NotAllowlisted
+        );
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
         require_gt!(args.amount, 0, AMMError::InvalidAmount);
 
         let (amount_x, amount_y) = match ctx.accounts.mint_lp.supply == 0
@@ -138,8 +211,36 @@ impl Deposit<'_> {
             AMMError::SlippageExceeded
         );
 
-        Deposit::transfer_tokens(&ctx, true, amount_x)?;
-        Deposit::transfer_tokens(&ctx, false, amount_y)?;
+        Deposit::transfer_tokens(&ctx, true, amount_x, args.wrap_sol)?;
+        Deposit::transfer_tokens(&ctx, false, amount_y, args.wrap_sol)?;
+
+        if let Some(position) = ctx.accounts.position.as_mut() {
+            position.settle(&ctx.accounts.config);
+            position.lp_amount = position
+                .lp_amount
+                .checked_add(args.amount)
+                .ok_or(AMMError::MathOverflow)?;
+            position.cost_x = position.cost_x.saturating_add(amount_x);
+            position.cost_y = position.cost_y.saturating_add(amount_y);
+        }
+
+        let supply = ctx.accounts.mint_lp.supply;
+        ctx.accounts.config.scale_virtual_y(
+            supply,
+            supply
+                .checked_add(args.amount)
+                .ok_or(AMMError::MathOverflow)?,
+        )?;
+
+        let to = match ctx.accounts.recipient {
+            Some(_) => ctx
+                .accounts
+                .recipient_lp
+                .as_ref()
+                .map(|ata| ata.to_account_info())
+                .ok_or(AMMError::MissingRecipientAccount)?,
+            None => ctx.accounts.user_lp.to_account_info(),
+        };
 
         let signer_seeds: &[&[&[u8]]] = &[&[
             CONFIG_SEED,
@@ -153,7 +254,7 @@ impl Deposit<'_> {
                 MintTo {
                     authority: ctx.accounts.config.to_account_info(),
                     mint: ctx.accounts.mint_lp.to_account_info(),
-                    to: ctx.accounts.user_lp.to_account_info(),
+                    to,
                 },
                 signer_seeds,
             ),
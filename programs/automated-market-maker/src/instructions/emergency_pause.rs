@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, Config, PAUSE_MASK};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EmergencyPauseArgs {
+    pub paused: u8,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    pub guardian: Signer<'info>,
+    #[account(
+        mut,
+        has_one = guardian @ AMMError::InvalidConfigGuardian,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl EmergencyPause<'_> {
+    /// Adds pause flags. Unpausing is left to the config authority.
+    pub fn handler(ctx: Context<EmergencyPause>, args: EmergencyPauseArgs) -> Result<()> {
+        require!(
+            args.paused != 0 && args.paused & !PAUSE_MASK == 0,
+            AMMError::InvalidPauseFlags
+        );
+
+        ctx.accounts.config.paused |= args.paused;
+
+        Ok(())
+    }
+}
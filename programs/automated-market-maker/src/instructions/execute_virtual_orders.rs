@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{utils::settle_virtual_orders, Config, Twamm, CONFIG_SEED, LP_SEED, TWAMM_SEED};
+
+#[derive(Accounts)]
+pub struct ExecuteVirtualOrders<'info> {
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, config.key().as_ref()],
+        bump = twamm.bump,
+    )]
+    pub twamm: Box<Account<'info, Twamm>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl ExecuteVirtualOrders<'_> {
+    /// Permissionless crank executing long-term orders up to now.
+    pub fn handler(ctx: Context<ExecuteVirtualOrders>) -> Result<()> {
+        let accounts = ctx.accounts;
+
+        settle_virtual_orders(
+            &mut accounts.config,
+            &mut accounts.twamm,
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            [&accounts.twamm_x, &accounts.twamm_y],
+            &accounts.token_program,
+        )
+    }
+}
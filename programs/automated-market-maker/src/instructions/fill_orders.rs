@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use constant_product_curve::{ConstantProduct, LiquidityPair};
+
+use crate::{
+    error::AMMError, utils::settle_twamm, Config, LimitOrder, Twamm, CONFIG_SEED, LP_SEED,
+    ORDER_SEED, PAUSE_SWAP,
+};
+
+/// Accounts expected per order in `remaining_accounts`:
+/// `[order, order_x, order_y]`.
+pub const ORDER_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct FillOrders<'info> {
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FillOrders<'info> {
+    /// Fills every open order whose `min_out` is met at the pool's current
+    /// price. Orders are filled in the order given; the rest are skipped.
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, FillOrders<'info>>) -> Result<()> {
+        ctx.accounts.config.invariant(PAUSE_SWAP)?;
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
+
+        let chunks = ctx.remaining_accounts.chunks_exact(ORDER_ACCOUNTS);
+        require!(
+            chunks.len() != 0 && chunks.remainder().is_empty(),
+            AMMError::InvalidOrderAccounts
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let token_program = ctx.accounts.token_program.key();
+
+        for accounts in chunks {
+            let mut order = Account::<LimitOrder>::try_from(&accounts[0])?;
+            require_keys_eq!(order.config, config_key, AMMError::InvalidOrderAccounts);
+            require_keys_eq!(
+                get_associated_token_address_with_program_id(
+                    &order.key(),
+                    &ctx.accounts.mint_x.key(),
+                    &token_program
+                ),
+                accounts[1].key(),
+                AMMError::InvalidOrderAccounts
+            );
+            require_keys_eq!(
+                get_associated_token_address_with_program_id(
+                    &order.key(),
+                    &ctx.accounts.mint_y.key(),
+                    &token_program
+                ),
+                accounts[2].key(),
+                AMMError::InvalidOrderAccounts
+            );
+
+            if order.filled {
+                continue;
+            }
+
+            let reserve_x = ctx.accounts.vault_x.amount;
+            let reserve_y = ctx.accounts.config.reserve_y(ctx.accounts.vault_y.amount)?;
+            let fee = ctx.accounts.config.swap_fee(Clock::get()?.unix_timestamp);
+
+            let mut curve =
+                ConstantProduct::init(reserve_x, reserve_y, ctx.accounts.mint_lp.supply, fee, None)
+                    .unwrap();
+
+            let p = match order.is_x {
+                true => LiquidityPair::X,
+                false => LiquidityPair::Y,
+            };
+
+            let res = curve.swap(p, order.amount, 0).unwrap();
+
+            if res.deposit == 0 || res.withdraw < order.min_out {
+                continue;
+            }
+            if order.is_x && ctx.accounts.vault_y.amount < res.withdraw {
+                continue;
+            }
+
+            let reserves_after = match order.is_x {
+                true => (reserve_x + res.deposit, reserve_y - res.withdraw),
+                false => (reserve_x - res.withdraw, reserve_y + res.deposit),
+            };
+            // orders that would trip the circuit breaker wait for a later fill
+            if ctx
+                .accounts
+                .config
+                .check_price_impact((reserve_x, reserve_y), reserves_after, Clock::get()?.slot)
+                .is_err()
+            {
+                continue;
+            }
+            ctx.accounts
+                .config
+                .record_price_move((reserve_x, reserve_y), reserves_after);
+            ctx.accounts
+                .config
+                .accrue_fee(order.is_x, res.fee, ctx.accounts.mint_lp.supply);
+
+            let (order_in, vault_in, mint_in, decimals_in) = match order.is_x {
+                true => (
+                    &accounts[1],
+                    ctx.accounts.vault_x.to_account_info(),
+                    ctx.accounts.mint_x.to_account_info(),
+                    ctx.accounts.mint_x.decimals,
+                ),
+                false => (
+                    &accounts[2],
+                    ctx.accounts.vault_y.to_account_info(),
+                    ctx.accounts.mint_y.to_account_info(),
+                    ctx.accounts.mint_y.decimals,
+                ),
+            };
+
+            let order_seeds: &[&[&[u8]]] = &[&[
+                ORDER_SEED,
+                order.config.as_ref(),
+                order.owner.as_ref(),
+                &order.id.to_le_bytes(),
+                &[order.bump],
+            ]];
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        authority: order.to_account_info(),
+                        from: order_in.clone(),
+                        to: vault_in,
+                        mint: mint_in,
+                    },
+                    order_seeds,
+                ),
+                res.deposit,
+                decimals_in,
+            )?;
+
+            let (vault_out, order_out, mint_out, decimals_out) = match order.is_x {
+                true => (
+                    ctx.accounts.vault_y.to_account_info(),
+                    &accounts[2],
+                    ctx.accounts.mint_y.to_account_info(),
+                    ctx.accounts.mint_y.decimals,
+                ),
+                false => (
+                    ctx.accounts.vault_x.to_account_info(),
+                    &accounts[1],
+                    ctx.accounts.mint_x.to_account_info(),
+                    ctx.accounts.mint_x.decimals,
+                ),
+            };
+
+            let config_seeds: &[&[&[u8]]] = &[&[
+                CONFIG_SEED,
+                &ctx.accounts.config.seed.to_le_bytes(),
+                &[ctx.accounts.config.bump],
+            ]];
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        authority: ctx.accounts.config.to_account_info(),
+                        from: vault_out,
+                        to: order_out.clone(),
+                        mint: mint_out,
+                    },
+                    config_seeds,
+                ),
+                res.withdraw,
+                decimals_out,
+            )?;
+
+            ctx.accounts.vault_x.reload()?;
+            ctx.accounts.vault_y.reload()?;
+
+            order.filled = true;
+            order.exit(&crate::ID)?;
+        }
+
+        Ok(())
+    }
+}
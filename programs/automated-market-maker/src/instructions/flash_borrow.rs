@@ -0,0 +1,156 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+        sysvar::instructions::{
+            load_current_index_checked, load_instruction_at_checked, ID as INSTRUCTIONS_SYSVAR_ID,
+        },
+    },
+    Discriminator,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, instruction, Config, CONFIG_SEED, PAUSE_FLASH};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FlashBorrowArgs {
+    is_x: bool,
+    amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl FlashBorrow<'_> {
+    /// Ensures a `flash_repay` for the same pool appears later in the transaction.
+    fn check_repay(ctx: &Context<FlashBorrow>) -> Result<()> {
+        require_eq!(
+            get_stack_height(),
+            TRANSACTION_LEVEL_STACK_HEIGHT,
+            AMMError::FlashBorrowCpi
+        );
+
+        let ixs = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&ixs)? as usize;
+        let mut index = current_index + 1;
+
+        while let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::ID
+                && ix.data.starts_with(instruction::FlashRepay::DISCRIMINATOR)
+                && ix
+                    .accounts
+                    .get(1)
+                    .is_some_and(|meta| meta.pubkey == ctx.accounts.config.key())
+            {
+                return Ok(());
+            }
+
+            index += 1;
+        }
+
+        err!(AMMError::MissingFlashRepay)
+    }
+
+    pub fn handler(ctx: Context<FlashBorrow>, args: FlashBorrowArgs) -> Result<()> {
+        Config::invariant(&ctx.accounts.config, PAUSE_FLASH)?;
+        ctx.accounts.config.check_permissionless()?;
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+
+        FlashBorrow::check_repay(&ctx)?;
+
+        let (from, to, mint, decimals) = match args.is_x {
+            true => (
+                ctx.accounts.vault_x.to_account_info(),
+                ctx.accounts.user_x.to_account_info(),
+                ctx.accounts.mint_x.to_account_info(),
+                ctx.accounts.mint_x.decimals,
+            ),
+            false => (
+                ctx.accounts.vault_y.to_account_info(),
+                ctx.accounts.user_y.to_account_info(),
+                ctx.accounts.mint_y.to_account_info(),
+                ctx.accounts.mint_y.decimals,
+            ),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CONFIG_SEED,
+            &ctx.accounts.config.seed.to_le_bytes(),
+            &[ctx.accounts.config.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.config.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+                signer_seeds,
+            ),
+            args.amount,
+            decimals,
+        )?;
+
+        ctx.accounts.config.flash_is_x = args.is_x;
+        ctx.accounts.config.flash_amount = args.amount;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    error::AMMError, Config, ProgramConfig, CONFIG_SEED, LP_SEED, MAX_FEE_BPS, PROGRAM_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::authority = program_config.admin,
+        token::token_program = token_program,
+    )]
+    pub protocol: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl FlashRepay<'_> {
+    fn transfer_tokens(
+        ctx: &Context<FlashRepay>,
+        is_x: bool,
+        to_protocol: bool,
+        amount: u64,
+    ) -> Result<()> {
+        let (from, to, mint, decimals) = match is_x {
+            true => (
+                ctx.accounts.user_x.to_account_info(),
+                ctx.accounts.vault_x.to_account_info(),
+                ctx.accounts.mint_x.to_account_info(),
+                ctx.accounts.mint_x.decimals,
+            ),
+            false => (
+                ctx.accounts.user_y.to_account_info(),
+                ctx.accounts.vault_y.to_account_info(),
+                ctx.accounts.mint_y.to_account_info(),
+                ctx.accounts.mint_y.decimals,
+            ),
+        };
+
+        let to = match to_protocol {
+            true => {
+                let protocol = ctx
+                    .accounts
+                    .protocol
+                    .as_ref()
+                    .ok_or(AMMError::MissingProtocolAccount)?;
+                require_keys_eq!(protocol.mint, mint.key(), AMMError::MissingProtocolAccount);
+
+                protocol.to_account_info()
+            }
+            false => to,
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.user.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+            ),
+            amount,
+            decimals,
+        )
+    }
+
+    pub fn handler(ctx: Context<FlashRepay>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require_gt!(config.flash_amount, 0, AMMError::NoFlashLoan);
+
+        let is_x = config.flash_is_x;
+        let amount = config.flash_amount;
+
+        // round up so that small loans cannot avoid the fee
+        let fee = (amount as u128)
+            .checked_mul(config.flash_fee as u128)
+            .and_then(|v| v.checked_add(MAX_FEE_BPS as u128 - 1))
+            .map(|v| v / MAX_FEE_BPS as u128)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(AMMError::MathOverflow)?;
+        let protocol_fee = fee
+            .checked_mul(config.protocol_fee as u64)
+            .map(|v| v / MAX_FEE_BPS as u64)
+            .ok_or(AMMError::MathOverflow)?;
+
+        FlashRepay::transfer_tokens(
+            &ctx,
+            is_x,
+            false,
+            amount
+                .checked_add(fee - protocol_fee)
+                .ok_or(AMMError::MathOverflow)?,
+        )?;
+
+        if protocol_fee > 0 {
+            FlashRepay::transfer_tokens(&ctx, is_x, true, protocol_fee)?;
+        }
+
+        ctx.accounts
+            .config
+            .accrue_fee(is_x, fee - protocol_fee, ctx.accounts.mint_lp.supply);
+
+        ctx.accounts.config.flash_is_x = false;
+        ctx.accounts.config.flash_amount = 0;
+
+        Ok(())
+    }
+}
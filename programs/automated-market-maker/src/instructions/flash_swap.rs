@@ -0,0 +1,356 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::AMMError, utils::settle_twamm, Config, SwapRecord, Twamm, CONFIG_SEED,
+    FLASH_SWAP_CALLBACK_DISCRIMINATOR, LP_SEED, MAX_FEE_BPS, PAUSE_FLASH, PAUSE_SWAP,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FlashSwapArgs {
+    is_x: bool,
+    amount_out: u64,
+    max_in: u64,
+    data: Vec<u8>,
+}
+
+/// Payload sent to the callback program after `FLASH_SWAP_CALLBACK_DISCRIMINATOR`.
+///
+/// The callback receives `[user, config, mint_x, mint_y, vault_x, vault_y,
+/// user_x, user_y, token_program]` followed by the flash swap's remaining
+/// accounts, and must transfer at least `amount_in` of the input mint into
+/// the pool's vault before returning.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FlashSwapCallbackArgs {
+    pub is_x: bool,
+    pub amount_out: u64,
+    pub amount_in: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Arbitrary program chosen by the user, invoked without pool signer seeds
+    #[account(
+        executable,
+        constraint = callback_program.key() != crate::ID @ AMMError::InvalidCallbackProgram,
+    )]
+    pub callback_program: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = config,
+        constraint = swap_record.owner == user.key() @ AMMError::InvalidSwapRecord,
+    )]
+    pub swap_record: Option<Box<Account<'info, SwapRecord>>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashSwap<'info> {
+    /// Input required to take `amount_out` from the pool with the swap fee applied.
+    fn amount_in(reserve_in: u64, reserve_out: u64, amount_out: u64, fee: u16) -> Result<u64> {
+        let numerator = (reserve_in as u128)
+            .checked_mul(amount_out as u128)
+            .and_then(|v| v.checked_mul(MAX_FEE_BPS as u128))
+            .ok_or(AMMError::MathOverflow)?;
+        let denominator = (reserve_out as u128)
+            .checked_sub(amount_out as u128)
+            .and_then(|v| v.checked_mul(MAX_FEE_BPS.checked_sub(fee)? as u128))
+            .filter(|v| *v != 0)
+            .ok_or(AMMError::InsufficientLiquidity)?;
+
+        u64::try_from(numerator.div_ceil(denominator)).map_err(|_| AMMError::MathOverflow.into())
+    }
+
+    /// Balance after a flash swap with the fee on any inflow deducted.
+    fn fee_adjusted(before: u64, after: u64, amount_out: u64, fee: u16) -> Result<u128> {
+        let amount_in = after.saturating_sub(before.saturating_sub(amount_out));
+        let fee_amount = (amount_in as u128)
+            .checked_mul(fee as u128)
+            .map(|v| v.div_ceil(MAX_FEE_BPS as u128))
+            .ok_or(AMMError::MathOverflow)?;
+
+        Ok((after as u128).saturating_sub(fee_amount))
+    }
+
+    fn invoke_callback(
+        ctx: &Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new(ctx.accounts.user.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.config.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.mint_x.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.mint_y.key(), false),
+            AccountMeta::new(ctx.accounts.vault_x.key(), false),
+            AccountMeta::new(ctx.accounts.vault_y.key(), false),
+            AccountMeta::new(ctx.accounts.user_x.key(), false),
+            AccountMeta::new(ctx.accounts.user_y.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+        ];
+        let mut infos = vec![
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.config.to_account_info(),
+            ctx.accounts.mint_x.to_account_info(),
+            ctx.accounts.mint_y.to_account_info(),
+            ctx.accounts.vault_x.to_account_info(),
+            ctx.accounts.vault_y.to_account_info(),
+            ctx.accounts.user_x.to_account_info(),
+            ctx.accounts.user_y.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+
+        for account in ctx.remaining_accounts {
+            accounts.push(match account.is_writable {
+                true => AccountMeta::new(account.key(), account.is_signer),
+                false => AccountMeta::new_readonly(account.key(), account.is_signer),
+            });
+            infos.push(account.clone());
+        }
+
+        infos.push(ctx.accounts.callback_program.to_account_info());
+
+        invoke(
+            &Instruction {
+                program_id: ctx.accounts.callback_program.key(),
+                accounts,
+                data,
+            },
+            &infos,
+        )
+        .map_err(Into::into)
+    }
+
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        args: FlashSwapArgs,
+    ) -> Result<()> {
+        Config::invariant(&ctx.accounts.config, PAUSE_SWAP | PAUSE_FLASH)?;
+        ctx.accounts.config.check_permissionless()?;
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
+        require_gt!(args.amount_out, 0, AMMError::InvalidAmount);
+
+        let x_before = ctx.accounts.vault_x.amount;
+        let y_before = ctx.accounts.vault_y.amount;
+        let reserve_x = x_before;
+        let reserve_y = ctx.accounts.config.reserve_y(y_before)?;
+        let clock = Clock::get()?;
+        let surcharge = ctx.accounts.config.sandwich_surcharge(
+            ctx.accounts.swap_record.as_deref_mut().map(|r| &mut **r),
+            args.is_x,
+            clock.slot,
+        )?;
+        let fee = ctx
+            .accounts
+            .config
+            .swap_fee(clock.unix_timestamp)
+            .saturating_add(surcharge)
+            .min(MAX_FEE_BPS);
+
+        let amount_in = match args.is_x {
+            true => {
+                require_gte!(y_before, args.amount_out, AMMError::InsufficientLiquidity);
+                FlashSwap::amount_in(reserve_x, reserve_y, args.amount_out, fee)?
+            }
+            false => FlashSwap::amount_in(reserve_y, reserve_x, args.amount_out, fee)?,
+        };
+
+        require_gte!(args.max_in, amount_in, AMMError::SlippageExceeded);
+
+        let (from, to, mint, decimals) = match args.is_x {
+            true => (
+                ctx.accounts.vault_y.to_account_info(),
+                ctx.accounts.user_y.to_account_info(),
+                ctx.accounts.mint_y.to_account_info(),
+                ctx.accounts.mint_y.decimals,
+            ),
+            false => (
+                ctx.accounts.vault_x.to_account_info(),
+                ctx.accounts.user_x.to_account_info(),
+                ctx.accounts.mint_x.to_account_info(),
+                ctx.accounts.mint_x.decimals,
+            ),
+        };
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CONFIG_SEED,
+            &ctx.accounts.config.seed.to_le_bytes(),
+            &[ctx.accounts.config.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.config.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+                signer_seeds,
+            ),
+            args.amount_out,
+            decimals,
+        )?;
+
+        // Persist the flash lock so the callback cannot re-enter the pool
+        // while its reserves are depleted.
+        ctx.accounts.config.flash_is_x = !args.is_x;
+        ctx.accounts.config.flash_amount = args.amount_out;
+        ctx.accounts.config.exit(&crate::ID)?;
+
+        let mut data = FLASH_SWAP_CALLBACK_DISCRIMINATOR.to_vec();
+        FlashSwapCallbackArgs {
+            is_x: args.is_x,
+            amount_out: args.amount_out,
+            amount_in,
+            data: args.data,
+        }
+        .serialize(&mut data)?;
+
+        FlashSwap::invoke_callback(&ctx, data)?;
+
+        ctx.accounts.config.reload()?;
+        ctx.accounts.vault_x.reload()?;
+        ctx.accounts.vault_y.reload()?;
+
+        let config = &ctx.accounts.config;
+        require!(
+            config.flash_is_x != args.is_x && config.flash_amount == args.amount_out,
+            AMMError::FlashLoanActive
+        );
+
+        let x_after = ctx.accounts.vault_x.amount;
+        let y_after = ctx.accounts.vault_y.amount;
+        let (x_out, y_out) = match args.is_x {
+            true => (0, args.amount_out),
+            false => (args.amount_out, 0),
+        };
+
+        let x_paid = x_after.saturating_sub(x_before.saturating_sub(x_out));
+        let y_paid = y_after.saturating_sub(y_before.saturating_sub(y_out));
+        let paid = match args.is_x {
+            true => x_paid,
+            false => y_paid,
+        };
+        require_gte!(args.max_in, paid, AMMError::SlippageExceeded);
+
+        let k_before = (reserve_x as u128)
+            .checked_mul(reserve_y as u128)
+            .ok_or(AMMError::MathOverflow)?;
+        let k_after = FlashSwap::fee_adjusted(x_before, x_after, x_out, fee)?
+            .checked_mul(
+                FlashSwap::fee_adjusted(y_before, y_after, y_out, fee)?
+                    .checked_add(config.virtual_y as u128)
+                    .ok_or(AMMError::MathOverflow)?,
+            )
+            .ok_or(AMMError::MathOverflow)?;
+
+        require_gte!(k_after, k_before, AMMError::InvariantViolated);
+
+        let fee_paid = (paid as u128)
+            .checked_mul(fee as u128)
+            .map(|v| v / MAX_FEE_BPS as u128)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(AMMError::MathOverflow)?;
+
+        let reserve_y_after = ctx.accounts.config.reserve_y(y_after)?;
+        ctx.accounts.config.check_price_impact(
+            (reserve_x, reserve_y),
+            (x_after, reserve_y_after),
+            clock.slot,
+        )?;
+        ctx.accounts
+            .config
+            .record_price_move((reserve_x, reserve_y), (x_after, reserve_y_after));
+        ctx.accounts
+            .config
+            .accrue_fee(args.is_x, fee_paid, ctx.accounts.mint_lp.supply);
+
+        ctx.accounts.config.flash_is_x = false;
+        ctx.accounts.config.flash_amount = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{Pair, PAIR_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetPoolArgs {
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub fee: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(args: GetPoolArgs)]
+pub struct GetPool<'info> {
+    #[account(
+        seeds = [
+            PAIR_SEED,
+            Pair::sorted(&args.mint_x, &args.mint_y)[0],
+            Pair::sorted(&args.mint_x, &args.mint_y)[1],
+            args.fee.to_le_bytes().as_ref(),
+        ],
+        bump = pair.bump,
+    )]
+    pub pair: Account<'info, Pair>,
+}
+
+impl GetPool<'_> {
+    pub fn handler(ctx: Context<GetPool>, _args: GetPoolArgs) -> Result<Pubkey> {
+        Ok(ctx.accounts.pair.config)
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, LpPosition, POSITION_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PositionFees {
+    pub fee_x: u64,
+    pub fee_y: u64,
+}
+
+#[derive(Accounts)]
+pub struct GetPositionFees<'info> {
+    pub config: Account<'info, Config>,
+    #[account(
+        has_one = config,
+        seeds = [POSITION_SEED, config.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, LpPosition>,
+}
+
+impl GetPositionFees<'_> {
+    pub fn handler(ctx: Context<GetPositionFees>) -> Result<PositionFees> {
+        let (fee_x, fee_y) = ctx.accounts.position.earned(&ctx.accounts.config);
+
+        Ok(PositionFees { fee_x, fee_y })
+    }
+}
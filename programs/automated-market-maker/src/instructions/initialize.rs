@@ -4,13 +4,21 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::{Config, CONFIG_SEED, LP_SEED};
+use crate::{
+    error::AMMError, Config, DiscountTier, Factory, Pair, ProgramConfig, CONFIG_RESERVED,
+    CONFIG_SEED, CONFIG_VERSION, FACTORY_SEED, LP_SEED, MAX_DISCOUNT_TIERS, MAX_FEE_BPS, PAIR_SEED,
+    PAUSE_MASK, PROGRAM_CONFIG_SEED, SANDWICH_GUARD_OFF,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeArgs {
     pub seed: u64,
-    pub locked: bool,
+    pub paused: u8,
     pub fee: u16,
+    pub virtual_y: u64,
+    pub flash_fee: u16,
+    pub dynamic_fee: bool,
+    pub max_fee: u16,
 }
 
 #[derive(Accounts)]
@@ -56,6 +64,30 @@ pub struct Initialize<'info> {
         associated_token::token_program = token_program,
     )]
     pub vault_y: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [FACTORY_SEED],
+        bump = factory.bump,
+    )]
+    pub factory: Box<Account<'info, Factory>>,
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+    #[account(
+        init,
+        payer = authority,
+        space = Pair::DISCRIMINATOR.len() + Pair::INIT_SPACE,
+        seeds = [
+            PAIR_SEED,
+            Pair::sorted(mint_x.to_account_info().key, mint_y.to_account_info().key)[0],
+            Pair::sorted(mint_x.to_account_info().key, mint_y.to_account_info().key)[1],
+            args.fee.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pair: Box<Account<'info, Pair>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -63,17 +95,72 @@ pub struct Initialize<'info> {
 
 impl Initialize<'_> {
     pub fn handler(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
+        require!(
+            ctx.accounts.program_config.is_fee_tier_enabled(args.fee),
+            AMMError::InvalidFeeTier
+        );
+        require_gte!(MAX_FEE_BPS, args.flash_fee, AMMError::InvalidFee);
+        require!(args.paused & !PAUSE_MASK == 0, AMMError::InvalidPauseFlags);
+
+        if args.dynamic_fee {
+            require_gte!(args.max_fee, args.fee, AMMError::InvalidFee);
+            require_gte!(MAX_FEE_BPS, args.max_fee, AMMError::InvalidFee);
+        }
+
         ctx.accounts.config.set_inner(Config {
+            version: CONFIG_VERSION,
             seed: args.seed,
-            locked: args.locked,
+            paused: args.paused,
             bump: ctx.bumps.config,
             lp_bump: ctx.bumps.mint_lp,
             fee: args.fee,
+            virtual_y: args.virtual_y,
+            flash_fee: args.flash_fee,
+            protocol_fee: ctx.accounts.program_config.protocol_fee,
+            flash_is_x: false,
+            flash_amount: 0,
+            dynamic_fee: args.dynamic_fee,
+            max_fee: args.max_fee,
+            volatility: 0,
+            volatility_updated_at: Clock::get()?.unix_timestamp,
             mint_x: ctx.accounts.mint_x.key(),
             mint_y: ctx.accounts.mint_y.key(),
             authority: ctx.accounts.authority.key(),
+            guardian: Pubkey::default(),
+            fee_growth_x: 0,
+            fee_growth_y: 0,
+            twamm: false,
+            max_swap_impact: 0,
+            max_slot_impact: 0,
+            slot: 0,
+            slot_price: 0,
+            sandwich_guard: SANDWICH_GUARD_OFF,
+            sandwich_fee: 0,
+            permissioned: false,
+            allowlist_authority: Pubkey::default(),
+            discount_mint: Pubkey::default(),
+            discount_tiers: [DiscountTier::default(); MAX_DISCOUNT_TIERS],
+            referral_fee: 0,
+            legacy: false,
+            open_orders: 0,
+            reserved: [0; CONFIG_RESERVED],
         });
 
+        ctx.accounts.pair.set_inner(Pair {
+            config: ctx.accounts.config.key(),
+            mint_x: ctx.accounts.mint_x.key(),
+            mint_y: ctx.accounts.mint_y.key(),
+            fee: args.fee,
+            bump: ctx.bumps.pair,
+        });
+
+        ctx.accounts.factory.pool_count = ctx
+            .accounts
+            .factory
+            .pool_count
+            .checked_add(1)
+            .ok_or(AMMError::MathOverflow)?;
+
         Ok(())
     }
 }
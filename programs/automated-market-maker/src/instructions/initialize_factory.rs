@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{Factory, FACTORY_SEED};
+
+#[derive(Accounts)]
+pub struct InitializeFactory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = Factory::DISCRIMINATOR.len() + Factory::INIT_SPACE,
+        seeds = [FACTORY_SEED],
+        bump,
+    )]
+    pub factory: Account<'info, Factory>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitializeFactory<'_> {
+    pub fn handler(ctx: Context<InitializeFactory>) -> Result<()> {
+        ctx.accounts.factory.set_inner(Factory {
+            pool_count: 0,
+            bump: ctx.bumps.factory,
+        });
+
+        Ok(())
+    }
+}
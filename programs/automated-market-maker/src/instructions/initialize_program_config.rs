@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AMMError, program::AutomatedMarketMaker, FeeTier, ProgramConfig, MAX_FEE_BPS,
+    MAX_FEE_TIERS, PROGRAM_CONFIG_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeProgramConfigArgs {
+    pub protocol_fee: u16,
+    pub fee_tiers: Vec<u16>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::DISCRIMINATOR.len() + ProgramConfig::INIT_SPACE,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, AutomatedMarketMaker>,
+    /// Only the upgrade authority may claim the admin role.
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ AMMError::InvalidProgramAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitializeProgramConfig<'_> {
+    pub fn handler(
+        ctx: Context<InitializeProgramConfig>,
+        args: InitializeProgramConfigArgs,
+    ) -> Result<()> {
+        require_gte!(MAX_FEE_BPS, args.protocol_fee, AMMError::InvalidFee);
+        require_gte!(MAX_FEE_TIERS, args.fee_tiers.len(), AMMError::FeeTiersFull);
+
+        let mut fee_tiers: Vec<FeeTier> = Vec::with_capacity(args.fee_tiers.len());
+
+        for fee in args.fee_tiers {
+            require_gte!(MAX_FEE_BPS, fee, AMMError::InvalidFee);
+
+            if !fee_tiers.iter().any(|tier| tier.fee == fee) {
+                fee_tiers.push(FeeTier { fee, enabled: true });
+            }
+        }
+
+        ctx.accounts.program_config.set_inner(ProgramConfig {
+            admin: ctx.accounts.admin.key(),
+            protocol_fee: args.protocol_fee,
+            bump: ctx.bumps.program_config,
+            fee_tiers,
+        });
+
+        Ok(())
+    }
+}
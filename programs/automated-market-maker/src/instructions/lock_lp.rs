@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Config, LpLock, CONFIG_SEED, LOCK_SEED, LP_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LockLpArgs {
+    pub id: u64,
+    pub amount: u64,
+    pub unlock_at: i64,
+    /// Lock forever, leaving only `claim_locked_fees` to the owner.
+    pub permanent: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(args: LockLpArgs)]
+pub struct LockLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = user,
+        space = LpLock::DISCRIMINATOR.len() + LpLock::INIT_SPACE,
+        seeds = [
+            LOCK_SEED,
+            config.key().as_ref(),
+            user.key().as_ref(),
+            args.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub lock: Box<Account<'info, LpLock>>,
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = mint_lp,
+        associated_token::authority = lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lock_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl LockLp<'_> {
+    pub fn handler(ctx: Context<LockLp>, args: LockLpArgs) -> Result<()> {
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+
+        let unlock_at = match args.permanent {
+            true => i64::MAX,
+            false => {
+                require_gt!(
+                    args.unlock_at,
+                    Clock::get()?.unix_timestamp,
+                    AMMError::InvalidUnlockTime
+                );
+                args.unlock_at
+            }
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.user.to_account_info(),
+                    from: ctx.accounts.user_lp.to_account_info(),
+                    to: ctx.accounts.lock_lp.to_account_info(),
+                    mint: ctx.accounts.mint_lp.to_account_info(),
+                },
+            ),
+            args.amount,
+            ctx.accounts.mint_lp.decimals,
+        )?;
+
+        ctx.accounts.lock.set_inner(LpLock {
+            config: ctx.accounts.config.key(),
+            owner: ctx.accounts.user.key(),
+            id: args.id,
+            amount: args.amount,
+            unlock_at,
+            permanent: args.permanent,
+            fee_growth_x: ctx.accounts.config.fee_growth_x,
+            fee_growth_y: ctx.accounts.config.fee_growth_y,
+            bump: ctx.bumps.lock,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    error::AMMError, Config, ConfigV0, DiscountTier, CONFIG_RESERVED, CONFIG_V1_LEN,
+    CONFIG_VERSION, MAX_DISCOUNT_TIERS, PAUSE_ALL, SANDWICH_GUARD_OFF,
+};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: Deserialized manually since the account predates the current layout
+    #[account(mut, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateConfig<'_> {
+    /// Upgrades a v0 config, whose fields map onto defaults of a newly
+    /// initialized pool.
+    fn from_v0(old: ConfigV0) -> Result<Config> {
+        Ok(Config {
+            version: CONFIG_VERSION,
+            seed: old.seed,
+            paused: match old.locked {
+                true => PAUSE_ALL,
+                false => 0,
+            },
+            bump: old.bump,
+            lp_bump: old.lp_bump,
+            fee: old.fee,
+            virtual_y: 0,
+            flash_fee: 0,
+            protocol_fee: 0,
+            flash_is_x: false,
+            flash_amount: 0,
+            dynamic_fee: false,
+            max_fee: 0,
+            volatility: 0,
+            volatility_updated_at: Clock::get()?.unix_timestamp,
+            mint_x: old.mint_x,
+            mint_y: old.mint_y,
+            authority: old.authority,
+            guardian: Pubkey::default(),
+            fee_growth_x: 0,
+            fee_growth_y: 0,
+            twamm: false,
+            max_swap_impact: 0,
+            max_slot_impact: 0,
+            slot: 0,
+            slot_price: 0,
+            sandwich_guard: SANDWICH_GUARD_OFF,
+            sandwich_fee: 0,
+            permissioned: false,
+            allowlist_authority: Pubkey::default(),
+            discount_mint: Pubkey::default(),
+            discount_tiers: [DiscountTier::default(); MAX_DISCOUNT_TIERS],
+            referral_fee: 0,
+            legacy: true,
+            open_orders: 0,
+            reserved: [0; CONFIG_RESERVED],
+        })
+    }
+
+    /// Reallocs a v0 or v1 config to the current layout. v1 data is a prefix
+    /// of the current layout, so its new fields start zeroed.
+    pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+        let space = Config::DISCRIMINATOR.len() + Config::INIT_SPACE;
+
+        let config = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.starts_with(Config::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+
+            let body = &data[Config::DISCRIMINATOR.len()..];
+            match body.len() {
+                ConfigV0::LEN => Self::from_v0(ConfigV0::deserialize(&mut &body[..])?)?,
+                CONFIG_V1_LEN if body[0] == 1 => {
+                    let mut upgraded = data.to_vec();
+                    upgraded.resize(space, 0);
+
+                    let mut config = Config::try_deserialize(&mut &upgraded[..])?;
+                    config.version = CONFIG_VERSION;
+                    config
+                }
+                _ => return err!(AMMError::InvalidConfigVersion),
+            }
+        };
+
+        require_keys_eq!(
+            config.authority,
+            ctx.accounts.authority.key(),
+            AMMError::InvalidConfigAuthority
+        );
+
+        let lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(info.lamports());
+
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        info.resize(space)?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        config.try_serialize(&mut &mut data[..])
+    }
+}
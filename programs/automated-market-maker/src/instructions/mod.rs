@@ -1,11 +1,83 @@
+pub mod add_reward;
+pub mod add_to_allowlist;
+pub mod archive_twamm_expiry;
+pub mod cancel_order;
+pub mod claim_all;
+pub mod claim_locked_fees;
+pub mod claim_rewards;
+pub mod close_pool;
+pub mod create_farm;
+pub mod create_twamm;
 pub mod deposit;
+pub mod emergency_pause;
+pub mod execute_virtual_orders;
+pub mod fill_orders;
+pub mod flash_borrow;
+pub mod flash_repay;
+pub mod flash_swap;
+pub mod get_pool;
+pub mod get_position_fees;
 pub mod initialize;
+pub mod initialize_factory;
+pub mod initialize_program_config;
+pub mod lock_lp;
+pub mod migrate_config;
+pub mod open_position;
+pub mod open_swap_record;
+pub mod place_long_term_order;
+pub mod place_order;
+pub mod remove_from_allowlist;
+pub mod set_fee_discount;
+pub mod set_fee_tier;
+pub mod set_protocol_fee;
+pub mod settle_long_term_order;
+pub mod stake;
 pub mod swap;
+pub mod swap_route;
+pub mod unlock_lp;
+pub mod unstake;
 pub mod update;
+pub mod update_program_config;
 pub mod withdraw;
 
+pub use add_reward::*;
+pub use add_to_allowlist::*;
+pub use archive_twamm_expiry::*;
+pub use cancel_order::*;
+pub use claim_all::*;
+pub use claim_locked_fees::*;
+pub use claim_rewards::*;
+pub use close_pool::*;
+pub use create_farm::*;
+pub use create_twamm::*;
 pub use deposit::*;
+pub use emergency_pause::*;
+pub use execute_virtual_orders::*;
+pub use fill_orders::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use flash_swap::*;
+pub use get_pool::*;
+pub use get_position_fees::*;
 pub use initialize::*;
+pub use initialize_factory::*;
+pub use initialize_program_config::*;
+pub use lock_lp::*;
+pub use migrate_config::*;
+pub use open_position::*;
+pub use open_swap_record::*;
+pub use place_long_term_order::*;
+pub use place_order::*;
+pub use remove_from_allowlist::*;
+pub use set_fee_discount::*;
+pub use set_fee_tier::*;
+pub use set_protocol_fee::*;
+pub use settle_long_term_order::*;
+pub use stake::*;
 pub use swap::*;
+pub use swap_route::*;
+pub use unlock_lp::*;
+pub use unstake::*;
 pub use update::*;
+pub use update_program_config::*;
 pub use withdraw::*;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, LpPosition, CONFIG_SEED, POSITION_SEED};
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = user,
+        space = LpPosition::DISCRIMINATOR.len() + LpPosition::INIT_SPACE,
+        seeds = [POSITION_SEED, config.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, LpPosition>,
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenPosition<'_> {
+    /// Starts tracking deposits from now on. LP tokens held before opening
+    /// are not part of the position.
+    pub fn handler(ctx: Context<OpenPosition>) -> Result<()> {
+        ctx.accounts.position.set_inner(LpPosition {
+            config: ctx.accounts.config.key(),
+            owner: ctx.accounts.user.key(),
+            lp_amount: 0,
+            cost_x: 0,
+            cost_y: 0,
+            fee_growth_x: ctx.accounts.config.fee_growth_x,
+            fee_growth_y: ctx.accounts.config.fee_growth_y,
+            fees_x: 0,
+            fees_y: 0,
+            bump: ctx.bumps.position,
+        });
+
+        Ok(())
+    }
+}
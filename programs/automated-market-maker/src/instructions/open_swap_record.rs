@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, SwapRecord, CONFIG_SEED, SWAP_RECORD_SEED};
+
+#[derive(Accounts)]
+pub struct OpenSwapRecord<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = user,
+        space = SwapRecord::DISCRIMINATOR.len() + SwapRecord::INIT_SPACE,
+        seeds = [SWAP_RECORD_SEED, config.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub swap_record: Account<'info, SwapRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenSwapRecord<'_> {
+    /// Creates the record swaps have to pass while the sandwich guard is on.
+    pub fn handler(ctx: Context<OpenSwapRecord>) -> Result<()> {
+        ctx.accounts.swap_record.set_inner(SwapRecord {
+            config: ctx.accounts.config.key(),
+            owner: ctx.accounts.user.key(),
+            slot: 0,
+            is_x: false,
+            bump: ctx.bumps.swap_record,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::AMMError, utils::settle_virtual_orders, Config, LongTermOrder, Twamm, CONFIG_SEED,
+    LONG_TERM_ORDER_SEED, LP_SEED, PAUSE_SWAP, TWAMM_INTERVAL, TWAMM_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlaceLongTermOrderArgs {
+    pub id: u64,
+    pub is_x: bool,
+    /// Input sold per second.
+    pub sale_rate: u64,
+    /// Must be a multiple of `TWAMM_INTERVAL`.
+    pub end_time: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: PlaceLongTermOrderArgs)]
+pub struct PlaceLongTermOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, config.key().as_ref()],
+        bump = twamm.bump,
+    )]
+    pub twamm: Box<Account<'info, Twamm>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        space = LongTermOrder::DISCRIMINATOR.len() + LongTermOrder::INIT_SPACE,
+        seeds = [
+            LONG_TERM_ORDER_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            args.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub order: Box<Account<'info, LongTermOrder>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl PlaceLongTermOrder<'_> {
+    /// Escrows `sale_rate` for every second until `end_time` and starts
+    /// selling it into the pool.
+    pub fn handler(ctx: Context<PlaceLongTermOrder>, args: PlaceLongTermOrderArgs) -> Result<()> {
+        ctx.accounts.config.invariant(PAUSE_SWAP)?;
+        ctx.accounts.config.check_permissionless()?;
+        require_gt!(args.sale_rate, 0, AMMError::InvalidAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = &mut *ctx.accounts;
+
+        settle_virtual_orders(
+            &mut accounts.config,
+            &mut accounts.twamm,
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            [&accounts.twamm_x, &accounts.twamm_y],
+            &accounts.token_program,
+        )?;
+
+        // an empty pool cannot execute, so the order would start in the past
+        require_eq!(
+            accounts.twamm.last_executed,
+            now,
+            AMMError::InsufficientLiquidity
+        );
+        require!(
+            args.end_time > now && args.end_time % TWAMM_INTERVAL == 0,
+            AMMError::InvalidOrderExpiry
+        );
+
+        let amount = args
+            .sale_rate
+            .checked_mul((args.end_time - now) as u64)
+            .ok_or(AMMError::MathOverflow)?;
+
+        let (from, to, mint, decimals) = match args.is_x {
+            true => (
+                accounts.owner_x.to_account_info(),
+                accounts.twamm_x.to_account_info(),
+                accounts.mint_x.to_account_info(),
+                accounts.mint_x.decimals,
+            ),
+            false => (
+                accounts.owner_y.to_account_info(),
+                accounts.twamm_y.to_account_info(),
+                accounts.mint_y.to_account_info(),
+                accounts.mint_y.decimals,
+            ),
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: accounts.owner.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+            ),
+            amount,
+            decimals,
+        )?;
+
+        accounts
+            .twamm
+            .add_order(args.is_x, args.sale_rate, args.end_time)?;
+
+        accounts.order.set_inner(LongTermOrder {
+            config: accounts.config.key(),
+            owner: accounts.owner.key(),
+            id: args.id,
+            is_x: args.is_x,
+            sale_rate: args.sale_rate,
+            end_time: args.end_time,
+            earnings_per_rate: accounts
+                .twamm
+                .earnings_per_rate(args.is_x, args.end_time, None)?,
+            bump: ctx.bumps.order,
+        });
+        accounts.config.open_orders = accounts
+            .config
+            .open_orders
+            .checked_add(1)
+            .ok_or(AMMError::MathOverflow)?;
+
+        Ok(())
+    }
+}
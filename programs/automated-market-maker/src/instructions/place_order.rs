@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Config, LimitOrder, CONFIG_SEED, ORDER_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlaceOrderArgs {
+    pub id: u64,
+    pub is_x: bool,
+    pub amount: u64,
+    pub min_out: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: PlaceOrderArgs)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = owner,
+        space = LimitOrder::DISCRIMINATOR.len() + LimitOrder::INIT_SPACE,
+        seeds = [
+            ORDER_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            args.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = order,
+        associated_token::token_program = token_program,
+    )]
+    pub order_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = order,
+        associated_token::token_program = token_program,
+    )]
+    pub order_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl PlaceOrder<'_> {
+    pub fn handler(ctx: Context<PlaceOrder>, args: PlaceOrderArgs) -> Result<()> {
+        ctx.accounts.config.check_permissionless()?;
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+        require_gt!(args.min_out, 0, AMMError::InvalidMinAmount);
+
+        let (from, to, mint, decimals) = match args.is_x {
+            true => (
+                ctx.accounts.owner_x.to_account_info(),
+                ctx.accounts.order_x.to_account_info(),
+                ctx.accounts.mint_x.to_account_info(),
+                ctx.accounts.mint_x.decimals,
+            ),
+            false => (
+                ctx.accounts.owner_y.to_account_info(),
+                ctx.accounts.order_y.to_account_info(),
+                ctx.accounts.mint_y.to_account_info(),
+                ctx.accounts.mint_y.decimals,
+            ),
+        };
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.owner.to_account_info(),
+                    from,
+                    to,
+                    mint,
+                },
+            ),
+            args.amount,
+            decimals,
+        )?;
+
+        ctx.accounts.order.set_inner(LimitOrder {
+            config: ctx.accounts.config.key(),
+            owner: ctx.accounts.owner.key(),
+            id: args.id,
+            is_x: args.is_x,
+            amount: args.amount,
+            min_out: args.min_out,
+            filled: false,
+            bump: ctx.bumps.order,
+        });
+
+        ctx.accounts.config.open_orders = ctx
+            .accounts
+            .config
+            .open_orders
+            .checked_add(1)
+            .ok_or(AMMError::MathOverflow)?;
+
+        Ok(())
+    }
+}
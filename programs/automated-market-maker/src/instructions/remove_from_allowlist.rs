@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, AllowlistEntry, Config, ALLOWLIST_SEED};
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(mut)]
+    pub allowlist_authority: Signer<'info>,
+    #[account(has_one = allowlist_authority @ AMMError::InvalidConfigAuthority)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        close = allowlist_authority,
+        has_one = config,
+        seeds = [
+            ALLOWLIST_SEED,
+            config.key().as_ref(),
+            allowlist_entry.user.as_ref(),
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Box<Account<'info, AllowlistEntry>>,
+}
+
+impl RemoveFromAllowlist<'_> {
+    /// Revokes the entry; the wallet can no longer use the pool.
+    pub fn handler(_ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, Config, DiscountTier, MAX_DISCOUNT_TIERS, MAX_FEE_BPS};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeDiscountArgs {
+    /// Default pubkey turns discounts off.
+    pub mint: Pubkey,
+    pub tiers: [DiscountTier; MAX_DISCOUNT_TIERS],
+}
+
+#[derive(Accounts)]
+pub struct SetFeeDiscount<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority @ AMMError::InvalidConfigAuthority,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl SetFeeDiscount<'_> {
+    pub fn handler(ctx: Context<SetFeeDiscount>, args: SetFeeDiscountArgs) -> Result<()> {
+        let active = args
+            .tiers
+            .iter()
+            .take_while(|tier| tier.min_balance > 0)
+            .count();
+
+        require!(
+            args.tiers[active..]
+                .iter()
+                .all(|tier| tier.min_balance == 0)
+                && args.tiers[..active]
+                    .windows(2)
+                    .all(|w| w[0].min_balance < w[1].min_balance),
+            AMMError::InvalidDiscountTiers
+        );
+        require!(
+            args.tiers.iter().all(|tier| tier.fee <= MAX_FEE_BPS),
+            AMMError::InvalidFee
+        );
+
+        ctx.accounts.config.discount_mint = args.mint;
+        ctx.accounts.config.discount_tiers = args.tiers;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AMMError, FeeTier, ProgramConfig, MAX_FEE_BPS, MAX_FEE_TIERS, PROGRAM_CONFIG_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeTierArgs {
+    pub fee: u16,
+    pub enabled: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTier<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ AMMError::InvalidProgramAdmin,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl SetFeeTier<'_> {
+    pub fn handler(ctx: Context<SetFeeTier>, args: SetFeeTierArgs) -> Result<()> {
+        require_gte!(MAX_FEE_BPS, args.fee, AMMError::InvalidFee);
+
+        let fee_tiers = &mut ctx.accounts.program_config.fee_tiers;
+
+        match fee_tiers.iter_mut().find(|tier| tier.fee == args.fee) {
+            Some(tier) => tier.enabled = args.enabled,
+            None => {
+                require_gt!(MAX_FEE_TIERS, fee_tiers.len(), AMMError::FeeTiersFull);
+
+                fee_tiers.push(FeeTier {
+                    fee: args.fee,
+                    enabled: args.enabled,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
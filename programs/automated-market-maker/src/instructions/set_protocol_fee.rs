@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::AMMError, Config, ProgramConfig, CONFIG_SEED, MAX_FEE_BPS, PROGRAM_CONFIG_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetProtocolFeeArgs {
+    pub protocol_fee: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ AMMError::InvalidProgramAdmin,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl SetProtocolFee<'_> {
+    /// Overrides the protocol's share of a pool's flash fees, which starts at
+    /// the program default.
+    pub fn handler(ctx: Context<SetProtocolFee>, args: SetProtocolFeeArgs) -> Result<()> {
+        require_gte!(MAX_FEE_BPS, args.protocol_fee, AMMError::InvalidFee);
+        ctx.accounts.config.protocol_fee = args.protocol_fee;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::AMMError, utils::settle_virtual_orders, ArchivedExpiry, Config, LongTermOrder, Twamm,
+    CONFIG_SEED, LONG_TERM_ORDER_SEED, LP_SEED, TWAMM_SEED,
+};
+
+#[derive(Accounts)]
+/// Accounts shared by `withdraw_long_term_proceeds` and
+/// `cancel_long_term_order`.
+pub struct SettleLongTermOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_x,
+        has_one = mint_y,
+        seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, config.key().as_ref()],
+        bump = twamm.bump,
+    )]
+    pub twamm: Box<Account<'info, Twamm>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = config,
+        has_one = owner,
+        seeds = [
+            LONG_TERM_ORDER_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            order.id.to_le_bytes().as_ref(),
+        ],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, LongTermOrder>>,
+    /// Required once the order's expiry has been archived.
+    #[account(
+        has_one = twamm,
+        constraint = archived_expiry.end_time == order.end_time @ AMMError::InvalidArchivedExpiry,
+    )]
+    pub archived_expiry: Option<Box<Account<'info, ArchivedExpiry>>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_x,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_y,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SettleLongTermOrder<'_> {
+    /// Pays out proceeds earned so far and closes the order once it has
+    /// fully executed.
+    pub fn withdraw_proceeds(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+        let accounts = &mut *ctx.accounts;
+        accounts.pay_out(false)?;
+
+        let (is_x, end_time) = (accounts.order.is_x, accounts.order.end_time);
+        accounts.order.earnings_per_rate = accounts.twamm.earnings_per_rate(
+            is_x,
+            end_time,
+            accounts.archived_expiry.as_deref().map(|a| &**a),
+        )?;
+
+        if end_time <= accounts.twamm.last_executed {
+            accounts.close()?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops the order, paying out its proceeds and refunding unsold input.
+    pub fn cancel(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+        let accounts = &mut *ctx.accounts;
+        accounts.pay_out(true)?;
+        accounts.close()
+    }
+
+    /// Executes virtual orders, then transfers the order's proceeds and, when
+    /// `refund` is set, its unsold input to the owner.
+    fn pay_out(&mut self, refund: bool) -> Result<()> {
+        settle_virtual_orders(
+            &mut self.config,
+            &mut self.twamm,
+            self.mint_lp.supply,
+            [&self.mint_x, &self.mint_y],
+            [&mut self.vault_x, &mut self.vault_y],
+            [&self.twamm_x, &self.twamm_y],
+            &self.token_program,
+        )?;
+
+        let proceeds = self
+            .order
+            .proceeds(&self.twamm, self.archived_expiry.as_deref().map(|a| &**a))?;
+        let unsold = match refund {
+            true => self.order.unsold(&self.twamm),
+            false => 0,
+        };
+
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[TWAMM_SEED, self.twamm.config.as_ref(), &[self.twamm.bump]]];
+
+        // x sellers are paid in y and refunded in x
+        let (paid, refunded) = match self.order.is_x {
+            true => (
+                (&self.twamm_y, &self.owner_y, &self.mint_y),
+                (&self.twamm_x, &self.owner_x, &self.mint_x),
+            ),
+            false => (
+                (&self.twamm_x, &self.owner_x, &self.mint_x),
+                (&self.twamm_y, &self.owner_y, &self.mint_y),
+            ),
+        };
+
+        for ((from, to, mint), amount) in [(paid, proceeds), (refunded, unsold)] {
+            if amount == 0 {
+                continue;
+            }
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        authority: self.twamm.to_account_info(),
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        mint: mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the order from the TWAMM and returns its rent to the owner.
+    fn close(&mut self) -> Result<()> {
+        self.twamm
+            .remove_order(self.order.is_x, self.order.sale_rate, self.order.end_time)?;
+        self.order.close(self.owner.to_account_info())?;
+        self.config.open_orders = self.config.open_orders.saturating_sub(1);
+
+        Ok(())
+    }
+}
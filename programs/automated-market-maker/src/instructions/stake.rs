@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::AMMError, Farm, FarmStake, FARM_SEED, STAKE_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StakeArgs {
+    amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_lp,
+        seeds = [FARM_SEED, farm.config.as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub farm_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = FarmStake::DISCRIMINATOR.len() + FarmStake::INIT_SPACE,
+        seeds = [STAKE_SEED, farm.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub stake: Box<Account<'info, FarmStake>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl Stake<'_> {
+    pub fn handler(ctx: Context<Stake>, args: StakeArgs) -> Result<()> {
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+
+        let farm = &mut ctx.accounts.farm;
+        farm.update(Clock::get()?.unix_timestamp)?;
+
+        let stake = &mut ctx.accounts.stake;
+        stake.farm = farm.key();
+        stake.owner = ctx.accounts.user.key();
+        stake.bump = ctx.bumps.stake;
+        stake.settle(farm)?;
+
+        stake.amount = stake
+            .amount
+            .checked_add(args.amount)
+            .ok_or(AMMError::MathOverflow)?;
+        farm.total_staked = farm
+            .total_staked
+            .checked_add(args.amount)
+            .ok_or(AMMError::MathOverflow)?;
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: ctx.accounts.user.to_account_info(),
+                    from: ctx.accounts.user_lp.to_account_info(),
+                    to: ctx.accounts.farm_lp.to_account_info(),
+                    mint: ctx.accounts.mint_lp.to_account_info(),
+                },
+            ),
+            args.amount,
+            ctx.accounts.mint_lp.decimals,
+        )
+    }
+}
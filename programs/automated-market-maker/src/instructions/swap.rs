@@ -5,13 +5,20 @@ use anchor_spl::{
 };
 use constant_product_curve::{ConstantProduct, LiquidityPair};
 
-use crate::{error::AMMError, Config, CONFIG_SEED, LP_SEED};
+use crate::{
+    error::AMMError,
+    events::SwapEvent,
+    utils::{is_native_mint, settle_twamm, unwrap_sol, wrap_sol},
+    AllowlistEntry, Config, SwapRecord, Twamm, CONFIG_SEED, LP_SEED, MAX_FEE_BPS, PAUSE_SWAP,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SwapArgs {
     is_x: bool,
     amount: u64,
     min: u64,
+    /// Pay native SOL input from lamports and unwrap native SOL output.
+    wrap_sol: bool,
 }
 
 #[derive(Accounts)]
@@ -19,6 +26,7 @@ pub struct Swap<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
@@ -65,24 +73,118 @@ pub struct Swap<'info> {
         associated_token::token_program = token_program,
     )]
     pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Owner of the output token account, defaults to `user` when absent
+    pub recipient: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        has_one = config,
+        constraint = swap_record.owner == user.key() @ AMMError::InvalidSwapRecord,
+    )]
+    pub swap_record: Option<Box<Account<'info, SwapRecord>>>,
+    #[account(
+        has_one = config,
+        constraint = allowlist_entry.user == user.key() @ AMMError::NotAllowlisted,
+    )]
+    pub allowlist_entry: Option<Box<Account<'info, AllowlistEntry>>>,
+    #[account(
+        token::authority = user,
+        constraint = discount_account.mint == config.discount_mint @ AMMError::InvalidDiscountAccount,
+    )]
+    pub discount_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Token account of the input mint receiving the referral share of the fee.
+    #[account(mut)]
+    pub referrer: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-impl Swap<'_> {
+impl<'info> Swap<'info> {
+    /// Token account receiving the output, the recipient's when one is given.
+    fn output_account(&self, is_x: bool) -> Result<AccountInfo<'info>> {
+        let (recipient_ata, user_ata) = match is_x {
+            true => (&self.recipient_x, &self.user_x),
+            false => (&self.recipient_y, &self.user_y),
+        };
+
+        match self.recipient {
+            Some(_) => recipient_ata
+                .as_ref()
+                .map(|ata| ata.to_account_info())
+                .ok_or(AMMError::MissingRecipientAccount.into()),
+            None => Ok(user_ata.to_account_info()),
+        }
+    }
+
     pub fn handler(ctx: Context<Swap>, args: SwapArgs) -> Result<()> {
-        Config::invariant(&ctx.accounts.config)?;
+        Config::invariant(&ctx.accounts.config, PAUSE_SWAP)?;
+        require!(
+            !ctx.accounts.config.permissioned || ctx.accounts.allowlist_entry.is_some(),
+            AMMError::NotAllowlisted
+        );
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
         require_gt!(args.amount, 0, AMMError::InvalidAmount);
 
-        let mut curve = ConstantProduct::init(
-            ctx.accounts.vault_x.amount,
-            ctx.accounts.vault_y.amount,
-            ctx.accounts.mint_lp.supply,
-            ctx.accounts.config.fee,
-            None,
-        )
-        .unwrap();
+        let reserve_x = ctx.accounts.vault_x.amount;
+        let reserve_y = ctx.accounts.config.reserve_y(ctx.accounts.vault_y.amount)?;
+        let clock = Clock::get()?;
+        let surcharge = ctx.accounts.config.sandwich_surcharge(
+            ctx.accounts.swap_record.as_deref_mut().map(|r| &mut **r),
+            args.is_x,
+            clock.slot,
+        )?;
+        let fee = ctx.accounts.config.swap_fee(clock.unix_timestamp);
+        let fee = match &ctx.accounts.discount_account {
+            Some(account) => ctx.accounts.config.discounted_fee(fee, account.amount),
+            None => fee,
+        }
+        .saturating_add(surcharge)
+        .min(MAX_FEE_BPS);
+
+        let mut curve =
+            ConstantProduct::init(reserve_x, reserve_y, ctx.accounts.mint_lp.supply, fee, None)
+                .unwrap();
 
         let p = match args.is_x {
             true => LiquidityPair::X,
@@ -94,6 +196,43 @@ impl Swap<'_> {
         require_neq!(res.deposit, 0, AMMError::InvalidAmount);
         require_neq!(res.withdraw, 0, AMMError::InvalidAmount);
 
+        let referral = match &ctx.accounts.referrer {
+            Some(referrer) => {
+                let mint_in = match args.is_x {
+                    true => ctx.accounts.mint_x.key(),
+                    false => ctx.accounts.mint_y.key(),
+                };
+                require_keys_eq!(referrer.mint, mint_in, AMMError::InvalidReferrerAccount);
+
+                ctx.accounts.config.referral_share(res.fee)
+            }
+            None => 0,
+        };
+
+        if args.is_x {
+            require_gte!(
+                ctx.accounts.vault_y.amount,
+                res.withdraw,
+                AMMError::InsufficientLiquidity
+            );
+        }
+
+        let reserves_after = match args.is_x {
+            true => (reserve_x + res.deposit, reserve_y - res.withdraw),
+            false => (reserve_x - res.withdraw, reserve_y + res.deposit),
+        };
+        ctx.accounts.config.check_price_impact(
+            (reserve_x, reserve_y),
+            reserves_after,
+            clock.slot,
+        )?;
+        ctx.accounts
+            .config
+            .record_price_move((reserve_x, reserve_y), reserves_after);
+        ctx.accounts
+            .config
+            .accrue_fee(args.is_x, res.fee - referral, ctx.accounts.mint_lp.supply);
+
         let (from, to, mint, decimals) = match args.is_x {
             true => (
                 ctx.accounts.user_x.to_account_info(),
@@ -109,6 +248,34 @@ impl Swap<'_> {
             ),
         };
 
+        if args.wrap_sol && is_native_mint(mint.key) {
+            wrap_sol(
+                &ctx.accounts.system_program,
+                &ctx.accounts.token_program,
+                ctx.accounts.user.to_account_info(),
+                from.clone(),
+                res.deposit,
+            )?;
+        }
+
+        if let Some(referrer) = &ctx.accounts.referrer {
+            if referral > 0 {
+                transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            authority: ctx.accounts.user.to_account_info(),
+                            from: from.clone(),
+                            to: referrer.to_account_info(),
+                            mint: mint.clone(),
+                        },
+                    ),
+                    referral,
+                    decimals,
+                )?;
+            }
+        }
+
         transfer_checked(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -119,25 +286,29 @@ impl Swap<'_> {
                     mint,
                 },
             ),
-            res.deposit,
+            res.deposit - referral,
             decimals,
         )?;
 
         let (from, to, mint, decimals) = match args.is_x {
             true => (
                 ctx.accounts.vault_y.to_account_info(),
-                ctx.accounts.user_y.to_account_info(),
+                ctx.accounts.output_account(false)?,
                 ctx.accounts.mint_y.to_account_info(),
                 ctx.accounts.mint_y.decimals,
             ),
             false => (
                 ctx.accounts.vault_x.to_account_info(),
-                ctx.accounts.user_x.to_account_info(),
+                ctx.accounts.output_account(true)?,
                 ctx.accounts.mint_x.to_account_info(),
                 ctx.accounts.mint_x.decimals,
             ),
         };
 
+        // only the signer can close its own token account
+        let unwrap = args.wrap_sol && is_native_mint(mint.key) && ctx.accounts.recipient.is_none();
+        let output = to.clone();
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             CONFIG_SEED,
             &ctx.accounts.config.seed.to_le_bytes(),
@@ -157,6 +328,31 @@ impl Swap<'_> {
             ),
             res.withdraw,
             decimals,
-        )
+        )?;
+
+        if unwrap {
+            unwrap_sol(
+                &ctx.accounts.token_program,
+                ctx.accounts.user.to_account_info(),
+                output,
+            )?;
+        }
+
+        emit!(SwapEvent {
+            config: ctx.accounts.config.key(),
+            user: ctx.accounts.user.key(),
+            is_x: args.is_x,
+            amount_in: res.deposit,
+            amount_out: res.withdraw,
+            fee: res.fee,
+            referrer: ctx
+                .accounts
+                .referrer
+                .as_ref()
+                .map(|referrer| referrer.key()),
+            referral_fee: referral,
+        });
+
+        Ok(())
     }
 }
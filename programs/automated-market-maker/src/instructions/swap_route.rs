@@ -0,0 +1,349 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use constant_product_curve::{ConstantProduct, LiquidityPair};
+
+use crate::{
+    error::AMMError, events::SwapEvent, Config, CONFIG_SEED, LP_SEED, PAUSE_SWAP,
+    SANDWICH_GUARD_OFF,
+};
+
+/// Accounts expected per hop in `remaining_accounts`:
+/// `[config, mint_lp, vault_x, vault_y, mint_x, mint_y]`.
+pub const ROUTE_HOP_ACCOUNTS: usize = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapRouteArgs {
+    amount: u64,
+    min_out: u64,
+}
+
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mint::token_program = token_program)]
+    pub mint_in: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_out: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_in: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_out,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_out: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Receives the referral share of the first hop's fee, paid in `mint_in`.
+    #[account(
+        mut,
+        constraint = referrer.mint == mint_in.key() @ AMMError::InvalidReferrerAccount,
+    )]
+    pub referrer: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+struct RoutePool<'info> {
+    config: Box<Account<'info, Config>>,
+    mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    vault_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    vault_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    mint_x: Box<InterfaceAccount<'info, Mint>>,
+    mint_y: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> RoutePool<'info> {
+    /// Loads one hop and applies the same checks as the `Swap` constraints.
+    fn load(accounts: &'info [AccountInfo<'info>], token_program: &Pubkey) -> Result<Self> {
+        let config = Box::new(Account::<Config>::try_from(&accounts[0])?);
+        let mint_lp = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[1])?);
+        let vault_x = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?);
+        let vault_y = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+        let mint_x = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[4])?);
+        let mint_y = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[5])?);
+
+        let config_key = Pubkey::create_program_address(
+            &[
+                CONFIG_SEED,
+                config.seed.to_le_bytes().as_ref(),
+                &[config.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(config_key, config.key(), ErrorCode::ConstraintSeeds);
+
+        let mint_lp_key = Pubkey::create_program_address(
+            &[LP_SEED, config.key().as_ref(), &[config.lp_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(mint_lp_key, mint_lp.key(), ErrorCode::ConstraintSeeds);
+
+        require_keys_eq!(config.mint_x, mint_x.key(), ErrorCode::ConstraintHasOne);
+        require_keys_eq!(config.mint_y, mint_y.key(), ErrorCode::ConstraintHasOne);
+
+        for mint in [&accounts[4], &accounts[5]] {
+            require_keys_eq!(
+                *mint.owner,
+                *token_program,
+                ErrorCode::ConstraintMintTokenProgram
+            );
+        }
+
+        require_keys_eq!(
+            get_associated_token_address_with_program_id(
+                &config.key(),
+                &config.mint_x,
+                token_program
+            ),
+            vault_x.key(),
+            ErrorCode::AccountNotAssociatedTokenAccount
+        );
+        require_keys_eq!(
+            get_associated_token_address_with_program_id(
+                &config.key(),
+                &config.mint_y,
+                token_program
+            ),
+            vault_y.key(),
+            ErrorCode::AccountNotAssociatedTokenAccount
+        );
+
+        Ok(Self {
+            config,
+            mint_lp,
+            vault_x,
+            vault_y,
+            mint_x,
+            mint_y,
+        })
+    }
+}
+
+impl<'info> SwapRoute<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        args: SwapRouteArgs,
+    ) -> Result<()> {
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+
+        let chunks = ctx.remaining_accounts.chunks_exact(ROUTE_HOP_ACCOUNTS);
+        require!(
+            chunks.len() != 0 && chunks.remainder().is_empty(),
+            AMMError::InvalidRoute
+        );
+
+        let hops: Vec<&'info [AccountInfo<'info>]> = chunks.collect();
+
+        let mut mint_in = ctx.accounts.mint_in.key();
+        let mut amount = args.amount;
+
+        for (i, hop) in hops.iter().enumerate() {
+            let mut pool = RoutePool::load(hop, &ctx.accounts.token_program.key())?;
+            Config::invariant(&pool.config, PAUSE_SWAP)?;
+            pool.config.check_permissionless()?;
+            // hops carry no TWAMM accounts or swap records, so pools with
+            // long-term orders or the sandwich guard on must be swapped directly
+            require!(!pool.config.twamm, AMMError::MissingTwammAccounts);
+            require_eq!(
+                pool.config.sandwich_guard,
+                SANDWICH_GUARD_OFF,
+                AMMError::InvalidSwapRecord
+            );
+
+            let is_x = match mint_in {
+                mint if mint == pool.config.mint_x => true,
+                mint if mint == pool.config.mint_y => false,
+                _ => return err!(AMMError::InvalidRoute),
+            };
+
+            // Input from the previous hop has already landed in the vault, so
+            // price against the reserves as they were before it arrived.
+            let (reserve_x, reserve_y) = match (i, is_x) {
+                (0, _) => (pool.vault_x.amount, pool.vault_y.amount),
+                (_, true) => (
+                    pool.vault_x
+                        .amount
+                        .checked_sub(amount)
+                        .ok_or(AMMError::MathOverflow)?,
+                    pool.vault_y.amount,
+                ),
+                (_, false) => (
+                    pool.vault_x.amount,
+                    pool.vault_y
+                        .amount
+                        .checked_sub(amount)
+                        .ok_or(AMMError::MathOverflow)?,
+                ),
+            };
+
+            let reserve_y = pool.config.reserve_y(reserve_y)?;
+            let fee = pool.config.swap_fee(Clock::get()?.unix_timestamp);
+
+            let mut curve =
+                ConstantProduct::init(reserve_x, reserve_y, pool.mint_lp.supply, fee, None)
+                    .unwrap();
+
+            let p = match is_x {
+                true => LiquidityPair::X,
+                false => LiquidityPair::Y,
+            };
+
+            let res = curve.swap(p, amount, 0).unwrap();
+
+            require_neq!(res.deposit, 0, AMMError::InvalidAmount);
+            require_neq!(res.withdraw, 0, AMMError::InvalidAmount);
+
+            let referrer = ctx.accounts.referrer.as_ref().filter(|_| i == 0);
+            let referral = match referrer {
+                Some(_) => pool.config.referral_share(res.fee),
+                None => 0,
+            };
+
+            if is_x {
+                require_gte!(
+                    pool.vault_y.amount,
+                    res.withdraw,
+                    AMMError::InsufficientLiquidity
+                );
+            }
+
+            let reserves_after = match is_x {
+                true => (reserve_x + res.deposit, reserve_y - res.withdraw),
+                false => (reserve_x - res.withdraw, reserve_y + res.deposit),
+            };
+            pool.config.check_price_impact(
+                (reserve_x, reserve_y),
+                reserves_after,
+                Clock::get()?.slot,
+            )?;
+            pool.config
+                .record_price_move((reserve_x, reserve_y), reserves_after);
+            pool.config
+                .accrue_fee(is_x, res.fee - referral, pool.mint_lp.supply);
+            pool.config.exit(&crate::ID)?;
+
+            if i == 0 {
+                let to = match is_x {
+                    true => pool.vault_x.to_account_info(),
+                    false => pool.vault_y.to_account_info(),
+                };
+
+                if let Some(referrer) = referrer.filter(|_| referral > 0) {
+                    transfer_checked(
+                        CpiContext::new(
+                            ctx.accounts.token_program.to_account_info(),
+                            TransferChecked {
+                                authority: ctx.accounts.user.to_account_info(),
+                                from: ctx.accounts.user_in.to_account_info(),
+                                to: referrer.to_account_info(),
+                                mint: ctx.accounts.mint_in.to_account_info(),
+                            },
+                        ),
+                        referral,
+                        ctx.accounts.mint_in.decimals,
+                    )?;
+                }
+
+                transfer_checked(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            authority: ctx.accounts.user.to_account_info(),
+                            from: ctx.accounts.user_in.to_account_info(),
+                            to,
+                            mint: ctx.accounts.mint_in.to_account_info(),
+                        },
+                    ),
+                    res.deposit - referral,
+                    ctx.accounts.mint_in.decimals,
+                )?;
+            }
+
+            let (from, mint, decimals) = match is_x {
+                true => (
+                    pool.vault_y.to_account_info(),
+                    pool.mint_y.to_account_info(),
+                    pool.mint_y.decimals,
+                ),
+                false => (
+                    pool.vault_x.to_account_info(),
+                    pool.mint_x.to_account_info(),
+                    pool.mint_x.decimals,
+                ),
+            };
+
+            let mint_out = mint.key();
+
+            let to = match hops.get(i + 1) {
+                Some(next) => {
+                    let next_config = Account::<Config>::try_from(&next[0])?;
+
+                    match mint_out == next_config.mint_x {
+                        true => next[2].clone(),
+                        false => next[3].clone(),
+                    }
+                }
+                None => {
+                    require_keys_eq!(
+                        mint_out,
+                        ctx.accounts.mint_out.key(),
+                        AMMError::InvalidRoute
+                    );
+                    require_gte!(res.withdraw, args.min_out, AMMError::SlippageExceeded);
+
+                    ctx.accounts.user_out.to_account_info()
+                }
+            };
+
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                CONFIG_SEED,
+                &pool.config.seed.to_le_bytes(),
+                &[pool.config.bump],
+            ]];
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        authority: pool.config.to_account_info(),
+                        from,
+                        to,
+                        mint,
+                    },
+                    signer_seeds,
+                ),
+                res.withdraw,
+                decimals,
+            )?;
+
+            emit!(SwapEvent {
+                config: pool.config.key(),
+                user: ctx.accounts.user.key(),
+                is_x,
+                amount_in: res.deposit,
+                amount_out: res.withdraw,
+                fee: res.fee,
+                referrer: referrer.map(|referrer| referrer.key()),
+                referral_fee: referral,
+            });
+
+            mint_in = mint_out;
+            amount = res.withdraw;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{error::AMMError, Config, LpLock, LOCK_SEED, LP_SEED};
+
+#[derive(Accounts)]
+pub struct UnlockLp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump,
+    )]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = config,
+        has_one = owner,
+        seeds = [
+            LOCK_SEED,
+            config.key().as_ref(),
+            owner.key().as_ref(),
+            lock.id.to_le_bytes().as_ref(),
+        ],
+        bump = lock.bump,
+    )]
+    pub lock: Box<Account<'info, LpLock>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lock_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_lp,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl UnlockLp<'_> {
+    /// Returns the escrowed LP tokens, which carry every fee the pool earned
+    /// on them while locked, and closes the receipt.
+    pub fn handler(ctx: Context<UnlockLp>) -> Result<()> {
+        let lock = &ctx.accounts.lock;
+        require!(!lock.permanent, AMMError::LockActive);
+        require_gte!(
+            Clock::get()?.unix_timestamp,
+            lock.unlock_at,
+            AMMError::LockActive
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            LOCK_SEED,
+            lock.config.as_ref(),
+            lock.owner.as_ref(),
+            &lock.id.to_le_bytes(),
+            &[lock.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: lock.to_account_info(),
+                    from: ctx.accounts.lock_lp.to_account_info(),
+                    to: ctx.accounts.owner_lp.to_account_info(),
+                    mint: ctx.accounts.mint_lp.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.accounts.lock_lp.amount,
+            ctx.accounts.mint_lp.decimals,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.lock_lp.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: lock.to_account_info(),
+            },
+            signer_seeds,
+        ))
+    }
+}
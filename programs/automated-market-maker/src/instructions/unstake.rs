@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{error::AMMError, Farm, FarmStake, FARM_SEED, STAKE_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeArgs {
+    amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_lp,
+        seeds = [FARM_SEED, farm.config.as_ref()],
+        bump = farm.bump,
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    #[account(mint::token_program = token_program)]
+    pub mint_lp: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = farm,
+        associated_token::token_program = token_program,
+    )]
+    pub farm_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_lp: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = farm,
+        seeds = [STAKE_SEED, farm.key().as_ref(), user.key().as_ref()],
+        bump = stake.bump,
+    )]
+    pub stake: Box<Account<'info, FarmStake>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl Unstake<'_> {
+    pub fn handler(ctx: Context<Unstake>, args: UnstakeArgs) -> Result<()> {
+        require_gt!(args.amount, 0, AMMError::InvalidAmount);
+
+        let farm = &mut ctx.accounts.farm;
+        farm.update(Clock::get()?.unix_timestamp)?;
+
+        let stake = &mut ctx.accounts.stake;
+        stake.settle(farm)?;
+
+        stake.amount = stake
+            .amount
+            .checked_sub(args.amount)
+            .ok_or(AMMError::InsufficientStake)?;
+        farm.total_staked = farm
+            .total_staked
+            .checked_sub(args.amount)
+            .ok_or(AMMError::MathOverflow)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[FARM_SEED, farm.config.as_ref(), &[farm.bump]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    authority: farm.to_account_info(),
+                    from: ctx.accounts.farm_lp.to_account_info(),
+                    to: ctx.accounts.user_lp.to_account_info(),
+                    mint: ctx.accounts.mint_lp.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            args.amount,
+            ctx.accounts.mint_lp.decimals,
+        )
+    }
+}
@@ -1,12 +1,28 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::AMMError, Config};
+use crate::{
+    error::AMMError, Config, ProgramConfig, MAX_FEE_BPS, MAX_REFERRAL_FEE, PAUSE_MASK,
+    PROGRAM_CONFIG_SEED, SANDWICH_GUARD_REJECT,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UpdateConfigArgs {
-    pub locked: Option<bool>,
+    pub paused: Option<u8>,
     pub fee: Option<u16>,
+    pub flash_fee: Option<u16>,
     pub authority: Option<Pubkey>,
+    pub guardian: Option<Pubkey>,
+    /// Maximum price move per swap in basis points, 0 disables.
+    pub max_swap_impact: Option<u16>,
+    /// Maximum price move per slot in basis points, 0 disables.
+    pub max_slot_impact: Option<u16>,
+    /// One of the `SANDWICH_GUARD_*` modes.
+    pub sandwich_guard: Option<u8>,
+    pub sandwich_fee: Option<u16>,
+    pub permissioned: Option<bool>,
+    pub allowlist_authority: Option<Pubkey>,
+    /// Share of the swap fee paid to referrers, at most `MAX_REFERRAL_FEE`.
+    pub referral_fee: Option<u16>,
 }
 
 #[derive(Accounts)]
@@ -17,22 +33,83 @@ pub struct UpdateConfig<'info> {
         has_one = authority @ AMMError::InvalidConfigAuthority,
     )]
     pub config: Account<'info, Config>,
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
 }
 
 impl UpdateConfig<'_> {
     pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
-        if let Some(locked) = args.locked {
-            ctx.accounts.config.locked = locked;
+        if let Some(paused) = args.paused {
+            require!(paused & !PAUSE_MASK == 0, AMMError::InvalidPauseFlags);
+            ctx.accounts.config.paused = paused;
         }
 
         if let Some(fee) = args.fee {
+            // the `Pair` entry is keyed by the fee tier it was created in
+            require!(ctx.accounts.config.legacy, AMMError::FeeTierLocked);
+            require!(
+                ctx.accounts.program_config.is_fee_tier_enabled(fee),
+                AMMError::InvalidFeeTier
+            );
+
+            if ctx.accounts.config.dynamic_fee {
+                require_gte!(ctx.accounts.config.max_fee, fee, AMMError::InvalidFee);
+            }
+
             ctx.accounts.config.fee = fee;
         }
 
+        if let Some(flash_fee) = args.flash_fee {
+            require_gte!(MAX_FEE_BPS, flash_fee, AMMError::InvalidFee);
+            ctx.accounts.config.flash_fee = flash_fee;
+        }
+
         if let Some(authority) = args.authority {
             ctx.accounts.config.authority = authority;
         }
 
+        if let Some(guardian) = args.guardian {
+            ctx.accounts.config.guardian = guardian;
+        }
+
+        if let Some(max_swap_impact) = args.max_swap_impact {
+            ctx.accounts.config.max_swap_impact = max_swap_impact;
+        }
+
+        if let Some(max_slot_impact) = args.max_slot_impact {
+            ctx.accounts.config.max_slot_impact = max_slot_impact;
+        }
+
+        if let Some(sandwich_guard) = args.sandwich_guard {
+            require_gte!(
+                SANDWICH_GUARD_REJECT,
+                sandwich_guard,
+                AMMError::InvalidSandwichGuard
+            );
+            ctx.accounts.config.sandwich_guard = sandwich_guard;
+        }
+
+        if let Some(sandwich_fee) = args.sandwich_fee {
+            require_gte!(MAX_FEE_BPS, sandwich_fee, AMMError::InvalidFee);
+            ctx.accounts.config.sandwich_fee = sandwich_fee;
+        }
+
+        if let Some(permissioned) = args.permissioned {
+            ctx.accounts.config.permissioned = permissioned;
+        }
+
+        if let Some(allowlist_authority) = args.allowlist_authority {
+            ctx.accounts.config.allowlist_authority = allowlist_authority;
+        }
+
+        if let Some(referral_fee) = args.referral_fee {
+            require_gte!(MAX_REFERRAL_FEE, referral_fee, AMMError::InvalidFee);
+            ctx.accounts.config.referral_fee = referral_fee;
+        }
+
         Ok(())
     }
 }
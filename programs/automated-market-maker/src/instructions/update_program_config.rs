@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, ProgramConfig, MAX_FEE_BPS, PROGRAM_CONFIG_SEED};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateProgramConfigArgs {
+    pub protocol_fee: Option<u16>,
+    pub admin: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ AMMError::InvalidProgramAdmin,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl UpdateProgramConfig<'_> {
+    pub fn handler(ctx: Context<UpdateProgramConfig>, args: UpdateProgramConfigArgs) -> Result<()> {
+        if let Some(protocol_fee) = args.protocol_fee {
+            require_gte!(MAX_FEE_BPS, protocol_fee, AMMError::InvalidFee);
+            ctx.accounts.program_config.protocol_fee = protocol_fee;
+        }
+
+        if let Some(admin) = args.admin {
+            ctx.accounts.program_config.admin = admin;
+        }
+
+        Ok(())
+    }
+}
@@ -7,13 +7,19 @@ use anchor_spl::{
 };
 use constant_product_curve::{ConstantProduct, XYAmounts};
 
-use crate::{error::AMMError, Config, CONFIG_SEED, LP_SEED};
+use crate::{
+    error::AMMError,
+    utils::{is_native_mint, settle_twamm, unwrap_sol},
+    AllowlistEntry, Config, LpPosition, Twamm, CONFIG_SEED, LP_SEED, PAUSE_WITHDRAW,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct WithdrawArgs {
     amount: u64,
     min_x: u64,
     min_y: u64,
+    /// Unwrap native SOL output to lamports.
+    wrap_sol: bool,
 }
 
 #[derive(Accounts)]
@@ -21,6 +27,7 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
+        mut,
         has_one = mint_x,
         has_one = mint_y,
         seeds = [CONFIG_SEED, config.seed.to_le_bytes().as_ref()],
@@ -74,28 +81,97 @@ pub struct Withdraw<'info> {
         associated_token::token_program = token_program,
     )]
     pub user_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Owner of the withdrawn token accounts, defaults to `user` when absent
+    pub recipient: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_x,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_y,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        has_one = config,
+        constraint = position.owner == user.key() @ AMMError::InvalidPositionOwner,
+    )]
+    pub position: Option<Box<Account<'info, LpPosition>>>,
+    #[account(
+        has_one = config,
+        constraint = allowlist_entry.user == user.key() @ AMMError::NotAllowlisted,
+    )]
+    pub allowlist_entry: Option<Box<Account<'info, AllowlistEntry>>>,
+    #[account(mut, has_one = config)]
+    pub twamm: Option<Box<Account<'info, Twamm>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = twamm,
+        associated_token::token_program = token_program,
+    )]
+    pub twamm_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-impl Withdraw<'_> {
-    fn transfer_tokens(ctx: &Context<Withdraw>, is_x: bool, amount: u64) -> Result<()> {
+impl<'info> Withdraw<'info> {
+    /// Token account receiving withdrawn tokens, the recipient's when one is given.
+    fn output_account(&self, is_x: bool) -> Result<AccountInfo<'info>> {
+        let (recipient_ata, user_ata) = match is_x {
+            true => (&self.recipient_x, &self.user_x),
+            false => (&self.recipient_y, &self.user_y),
+        };
+
+        match self.recipient {
+            Some(_) => recipient_ata
+                .as_ref()
+                .map(|ata| ata.to_account_info())
+                .ok_or(AMMError::MissingRecipientAccount.into()),
+            None => Ok(user_ata.to_account_info()),
+        }
+    }
+
+    fn transfer_tokens(
+        ctx: &Context<Withdraw<'info>>,
+        is_x: bool,
+        amount: u64,
+        unwrap: bool,
+    ) -> Result<()> {
         let (from, to, mint, decimals) = match is_x {
             true => (
                 ctx.accounts.vault_x.to_account_info(),
-                ctx.accounts.user_x.to_account_info(),
+                ctx.accounts.output_account(true)?,
                 ctx.accounts.mint_x.to_account_info(),
                 ctx.accounts.mint_x.decimals,
             ),
             false => (
                 ctx.accounts.vault_y.to_account_info(),
-                ctx.accounts.user_y.to_account_info(),
+                ctx.accounts.output_account(false)?,
                 ctx.accounts.mint_y.to_account_info(),
                 ctx.accounts.mint_y.decimals,
             ),
         };
 
+        let mint_key = mint.key();
+        let output = to.clone();
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             CONFIG_SEED,
             &ctx.accounts.config.seed.to_le_bytes(),
@@ -115,14 +191,39 @@ impl Withdraw<'_> {
             ),
             amount,
             decimals,
-        )
+        )?;
+
+        // only the signer can close its own token account
+        if unwrap && is_native_mint(&mint_key) && ctx.accounts.recipient.is_none() {
+            unwrap_sol(
+                &ctx.accounts.token_program,
+                ctx.accounts.user.to_account_info(),
+                output,
+            )?;
+        }
+
+        Ok(())
     }
 
     pub fn handler(ctx: Context<Withdraw>, args: WithdrawArgs) -> Result<()> {
-        Config::invariant(&ctx.accounts.config)?;
+        Config::invariant(&ctx.accounts.config, PAUSE_WITHDRAW)?;
+        require!(
+            !ctx.accounts.config.permissioned || ctx.accounts.allowlist_entry.is_some(),
+            AMMError::NotAllowlisted
+        );
+        let accounts = &mut *ctx.accounts;
+        settle_twamm(
+            &mut accounts.config,
+            accounts.twamm.as_deref_mut(),
+            [accounts.twamm_x.as_deref(), accounts.twamm_y.as_deref()],
+            accounts.mint_lp.supply,
+            [&accounts.mint_x, &accounts.mint_y],
+            [&mut accounts.vault_x, &mut accounts.vault_y],
+            &accounts.token_program,
+        )?;
         require_gt!(args.amount, 0, AMMError::InvalidAmount);
         require!(
-            args.min_x != 0 && args.min_y != 0,
+            args.min_x != 0 && (args.min_y != 0 || ctx.accounts.config.virtual_y != 0),
             AMMError::InvalidMinAmount
         );
 
@@ -143,8 +244,33 @@ impl Withdraw<'_> {
             AMMError::SlippageExceeded
         );
 
-        Withdraw::transfer_tokens(&ctx, true, amount_x)?;
-        Withdraw::transfer_tokens(&ctx, false, amount_y)?;
+        Withdraw::transfer_tokens(&ctx, true, amount_x, args.wrap_sol)?;
+        Withdraw::transfer_tokens(&ctx, false, amount_y, args.wrap_sol)?;
+
+        if let Some(position) = ctx.accounts.position.as_mut() {
+            position.settle(&ctx.accounts.config);
+
+            // anything beyond the tracked amount came from LP held outside the position
+            let lp_amount = position.lp_amount;
+            let removed = args.amount.min(lp_amount);
+
+            if removed > 0 {
+                let reduce =
+                    |cost: u64| ((cost as u128) * removed as u128 / lp_amount as u128) as u64;
+
+                position.cost_x -= reduce(position.cost_x);
+                position.cost_y -= reduce(position.cost_y);
+                position.lp_amount -= removed;
+            }
+        }
+
+        let supply = ctx.accounts.mint_lp.supply;
+        ctx.accounts.config.scale_virtual_y(
+            supply,
+            supply
+                .checked_sub(args.amount)
+                .ok_or(AMMError::MathOverflow)?,
+        )?;
 
         burn(
             CpiContext::new(
@@ -1,7 +1,9 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -15,6 +17,32 @@ declare_id!("C7f2vQaRdp8oJR5R9P86CKn48wQkEmgtdkEpjZy1MaAi");
 pub mod automated_market_maker {
     use super::*;
 
+    pub fn initialize_factory(ctx: Context<InitializeFactory>) -> Result<()> {
+        InitializeFactory::handler(ctx)
+    }
+
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        args: InitializeProgramConfigArgs,
+    ) -> Result<()> {
+        InitializeProgramConfig::handler(ctx, args)
+    }
+
+    pub fn update_program_config(
+        ctx: Context<UpdateProgramConfig>,
+        args: UpdateProgramConfigArgs,
+    ) -> Result<()> {
+        UpdateProgramConfig::handler(ctx, args)
+    }
+
+    pub fn set_fee_tier(ctx: Context<SetFeeTier>, args: SetFeeTierArgs) -> Result<()> {
+        SetFeeTier::handler(ctx, args)
+    }
+
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, args: SetProtocolFeeArgs) -> Result<()> {
+        SetProtocolFee::handler(ctx, args)
+    }
+
     pub fn initialize(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
         Initialize::handler(ctx, args)
     }
@@ -23,6 +51,38 @@ pub mod automated_market_maker {
         UpdateConfig::update_config(ctx, args)
     }
 
+    pub fn set_fee_discount(ctx: Context<SetFeeDiscount>, args: SetFeeDiscountArgs) -> Result<()> {
+        SetFeeDiscount::handler(ctx, args)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        MigrateConfig::handler(ctx)
+    }
+
+    pub fn emergency_pause(ctx: Context<EmergencyPause>, args: EmergencyPauseArgs) -> Result<()> {
+        EmergencyPause::handler(ctx, args)
+    }
+
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        ClosePool::handler(ctx)
+    }
+
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>) -> Result<()> {
+        AddToAllowlist::handler(ctx)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        RemoveFromAllowlist::handler(ctx)
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        OpenPosition::handler(ctx)
+    }
+
+    pub fn open_swap_record(ctx: Context<OpenSwapRecord>) -> Result<()> {
+        OpenSwapRecord::handler(ctx)
+    }
+
     pub fn deposit(ctx: Context<Deposit>, args: DepositArgs) -> Result<()> {
         Deposit::handler(ctx, args)
     }
@@ -34,4 +94,112 @@ pub mod automated_market_maker {
     pub fn swap(ctx: Context<Swap>, args: SwapArgs) -> Result<()> {
         Swap::handler(ctx, args)
     }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        args: SwapRouteArgs,
+    ) -> Result<()> {
+        SwapRoute::handler(ctx, args)
+    }
+
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, args: FlashBorrowArgs) -> Result<()> {
+        FlashBorrow::handler(ctx, args)
+    }
+
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+        FlashRepay::handler(ctx)
+    }
+
+    pub fn flash_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        args: FlashSwapArgs,
+    ) -> Result<()> {
+        FlashSwap::handler(ctx, args)
+    }
+
+    pub fn place_order(ctx: Context<PlaceOrder>, args: PlaceOrderArgs) -> Result<()> {
+        PlaceOrder::handler(ctx, args)
+    }
+
+    pub fn fill_orders<'info>(ctx: Context<'_, '_, 'info, 'info, FillOrders<'info>>) -> Result<()> {
+        FillOrders::handler(ctx)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        CancelOrder::handler(ctx)
+    }
+
+    pub fn create_twamm(ctx: Context<CreateTwamm>) -> Result<()> {
+        CreateTwamm::handler(ctx)
+    }
+
+    pub fn execute_virtual_orders(ctx: Context<ExecuteVirtualOrders>) -> Result<()> {
+        ExecuteVirtualOrders::handler(ctx)
+    }
+
+    pub fn place_long_term_order(
+        ctx: Context<PlaceLongTermOrder>,
+        args: PlaceLongTermOrderArgs,
+    ) -> Result<()> {
+        PlaceLongTermOrder::handler(ctx, args)
+    }
+
+    pub fn withdraw_long_term_proceeds(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+        SettleLongTermOrder::withdraw_proceeds(ctx)
+    }
+
+    pub fn cancel_long_term_order(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+        SettleLongTermOrder::cancel(ctx)
+    }
+
+    pub fn archive_twamm_expiry(
+        ctx: Context<ArchiveTwammExpiry>,
+        args: ArchiveTwammExpiryArgs,
+    ) -> Result<()> {
+        ArchiveTwammExpiry::handler(ctx, args)
+    }
+
+    pub fn lock_lp(ctx: Context<LockLp>, args: LockLpArgs) -> Result<()> {
+        LockLp::handler(ctx, args)
+    }
+
+    pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+        UnlockLp::handler(ctx)
+    }
+
+    pub fn claim_locked_fees(ctx: Context<ClaimLockedFees>) -> Result<()> {
+        ClaimLockedFees::handler(ctx)
+    }
+
+    pub fn create_farm(ctx: Context<CreateFarm>) -> Result<()> {
+        CreateFarm::handler(ctx)
+    }
+
+    pub fn add_reward(ctx: Context<AddReward>, args: AddRewardArgs) -> Result<()> {
+        AddReward::handler(ctx, args)
+    }
+
+    pub fn stake(ctx: Context<Stake>, args: StakeArgs) -> Result<()> {
+        Stake::handler(ctx, args)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, args: UnstakeArgs) -> Result<()> {
+        Unstake::handler(ctx, args)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        ClaimRewards::handler(ctx)
+    }
+
+    pub fn claim_all<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAll<'info>>) -> Result<()> {
+        ClaimAll::handler(ctx)
+    }
+
+    pub fn get_pool(ctx: Context<GetPool>, args: GetPoolArgs) -> Result<Pubkey> {
+        GetPool::handler(ctx, args)
+    }
+
+    pub fn get_position_fees(ctx: Context<GetPositionFees>) -> Result<PositionFees> {
+        GetPositionFees::handler(ctx)
+    }
 }
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Approval for `user` to use a permissioned pool. Revoked by closing it.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+}
@@ -1,9 +1,81 @@
-use crate::error::AMMError;
+use crate::{
+    error::AMMError, SwapRecord, CONFIG_RESERVED, MAX_DISCOUNT_TIERS, MAX_FEE_BPS, PAUSE_ALL,
+    SANDWICH_GUARD_OFF, SANDWICH_GUARD_REJECT, VOLATILITY_FEE_DIVISOR, VOLATILITY_HALF_LIFE,
+};
 use anchor_lang::prelude::*;
 
+/// Swap fee for holders of at least `min_balance` of the discount mint. A
+/// zero `min_balance` marks an unused tier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct DiscountTier {
+    pub min_balance: u64,
+    pub fee: u16,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
+    pub version: u8,
+    pub seed: u64,
+    pub paused: u8,
+    pub bump: u8,
+    pub lp_bump: u8,
+    pub fee: u16,
+    pub virtual_y: u64,
+    pub flash_fee: u16,
+    pub protocol_fee: u16,
+    pub flash_is_x: bool,
+    pub flash_amount: u64,
+    pub dynamic_fee: bool,
+    pub max_fee: u16,
+    pub volatility: u64,
+    pub volatility_updated_at: i64,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub authority: Pubkey,
+    pub guardian: Pubkey,
+    /// Swap fees paid in x per LP token, as a Q64.64 value that wraps.
+    pub fee_growth_x: u128,
+    /// Swap fees paid in y per LP token, as a Q64.64 value that wraps.
+    pub fee_growth_y: u128,
+    /// Set once a TWAMM exists; swaps, deposits and withdrawals must then
+    /// execute its virtual orders first.
+    pub twamm: bool,
+    /// Largest price move a single swap may cause, in basis points. 0 disables.
+    pub max_swap_impact: u16,
+    /// Largest price move allowed within one slot, in basis points. 0 disables.
+    pub max_slot_impact: u16,
+    /// Slot of the first swap that `slot_price` was recorded for.
+    pub slot: u64,
+    /// Price of x in y at the start of `slot`, as a Q64.64 value.
+    pub slot_price: u128,
+    /// One of the `SANDWICH_GUARD_*` modes. When on, swaps must pass the
+    /// signer's `SwapRecord`.
+    pub sandwich_guard: u8,
+    /// Surcharge in basis points under `SANDWICH_GUARD_SURCHARGE`.
+    pub sandwich_fee: u16,
+    /// Only wallets with an `AllowlistEntry` may swap, deposit or withdraw.
+    pub permissioned: bool,
+    /// Creates and revokes allowlist entries.
+    pub allowlist_authority: Pubkey,
+    /// Mint whose holders get discounted swap fees, default when off.
+    pub discount_mint: Pubkey,
+    /// Sorted by ascending `min_balance`, unused tiers last.
+    pub discount_tiers: [DiscountTier; MAX_DISCOUNT_TIERS],
+    /// Share of the swap fee paid to a swap's referrer, in basis points of the
+    /// fee.
+    pub referral_fee: u16,
+    /// Migrated from the v0 layout, so the pool has no `Pair` registry entry.
+    pub legacy: bool,
+    /// Limit and long-term orders still holding escrowed tokens. The pool
+    /// cannot close while any are open.
+    pub open_orders: u32,
+    pub reserved: [u8; CONFIG_RESERVED],
+}
+
+/// Layout of pools created before `Config` was versioned.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigV0 {
     pub seed: u64,
     pub locked: bool,
     pub bump: u8,
@@ -14,10 +86,230 @@ pub struct Config {
     pub authority: Pubkey,
 }
 
+impl ConfigV0 {
+    pub const LEN: usize = 8 + 1 + 1 + 1 + 2 + 32 * 3;
+}
+
+/// Size of a v1 `Config`, which ended 3 reserved bytes after `slot_price`.
+/// Later versions only append fields, so v1 data is a prefix of the current
+/// layout.
+pub const CONFIG_V1_LEN: usize = 1
+    + 8
+    + 1
+    + 1
+    + 1
+    + 2
+    + 8
+    + 2
+    + 2
+    + 1
+    + 8
+    + 1
+    + 2
+    + 8
+    + 8
+    + 32 * 4
+    + 16 * 2
+    + 1
+    + 2
+    + 2
+    + 8
+    + 16
+    + 3;
+
 impl Config {
-    pub fn invariant(&self) -> Result<()> {
-        require!(self.locked == false, AMMError::PoolLocked);
+    /// Checks that none of `operations` are paused and no flash loan is active.
+    pub fn invariant(&self, operations: u8) -> Result<()> {
+        require!(
+            self.paused & (operations | PAUSE_ALL) == 0,
+            AMMError::OperationPaused
+        );
+        require!(self.flash_amount == 0, AMMError::FlashLoanActive);
 
         Ok(())
     }
+
+    /// Quote reserve used for pricing, including the virtual balance.
+    pub fn reserve_y(&self, vault_y: u64) -> Result<u64> {
+        vault_y
+            .checked_add(self.virtual_y)
+            .ok_or(AMMError::MathOverflow.into())
+    }
+
+    /// Scales the virtual quote reserve with LP supply so that deposits and
+    /// withdrawals leave the pool price unchanged.
+    pub fn scale_virtual_y(&mut self, supply: u64, new_supply: u64) -> Result<()> {
+        if supply == 0 || self.virtual_y == 0 {
+            return Ok(());
+        }
+
+        self.virtual_y = (self.virtual_y as u128)
+            .checked_mul(new_supply as u128)
+            .and_then(|v| v.checked_div(supply as u128))
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(AMMError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Fee for the next swap. In dynamic mode the volatility accumulator is
+    /// halved for every elapsed half-life before adding it to the base fee.
+    pub fn swap_fee(&mut self, now: i64) -> u16 {
+        if !self.dynamic_fee {
+            return self.fee;
+        }
+
+        let elapsed = now.saturating_sub(self.volatility_updated_at).max(0) as u64;
+        let halvings = elapsed / VOLATILITY_HALF_LIFE;
+
+        self.volatility = self
+            .volatility
+            .checked_shr(u32::try_from(halvings).unwrap_or(u32::MAX))
+            .unwrap_or(0);
+        self.volatility_updated_at = self
+            .volatility_updated_at
+            .saturating_add((halvings * VOLATILITY_HALF_LIFE) as i64);
+
+        (self.fee as u64)
+            .saturating_add(self.volatility / VOLATILITY_FEE_DIVISOR)
+            .min(self.max_fee as u64) as u16
+    }
+
+    /// Fee for a holder of `balance` discount tokens, never above `fee`.
+    pub fn discounted_fee(&self, fee: u16, balance: u64) -> u16 {
+        self.discount_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_balance > 0 && balance >= tier.min_balance)
+            .map_or(fee, |tier| tier.fee.min(fee))
+    }
+
+    /// Records the swap direction for the sandwich guard and returns the
+    /// surcharge owed for reversing direction within a slot.
+    pub fn sandwich_surcharge(
+        &self,
+        record: Option<&mut SwapRecord>,
+        is_x: bool,
+        slot: u64,
+    ) -> Result<u16> {
+        if self.sandwich_guard == SANDWICH_GUARD_OFF {
+            return Ok(0);
+        }
+
+        let record = record.ok_or(AMMError::InvalidSwapRecord)?;
+        let reversed = record.slot == slot && record.is_x != is_x;
+        record.slot = slot;
+        record.is_x = is_x;
+
+        match (reversed, self.sandwich_guard) {
+            (false, _) => Ok(0),
+            (true, SANDWICH_GUARD_REJECT) => err!(AMMError::SandwichDetected),
+            (true, _) => Ok(self.sandwich_fee),
+        }
+    }
+
+    /// Part of a swap's `fee` amount owed to its referrer, rounded down.
+    pub fn referral_share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.referral_fee as u128 / MAX_FEE_BPS as u128) as u64
+    }
+
+    /// Adds a swap fee paid in the input mint to the per-LP fee growth.
+    pub fn accrue_fee(&mut self, is_x: bool, fee: u64, supply: u64) {
+        if supply == 0 {
+            return;
+        }
+
+        let growth = ((fee as u128) << 64) / supply as u128;
+
+        match is_x {
+            true => self.fee_growth_x = self.fee_growth_x.wrapping_add(growth),
+            false => self.fee_growth_y = self.fee_growth_y.wrapping_add(growth),
+        }
+    }
+
+    /// Fees earned by `amount` LP tokens since the fee growth checkpoint.
+    pub fn fees_earned(&self, amount: u64, fee_growth_x: u128, fee_growth_y: u128) -> (u64, u64) {
+        let earned = |growth: u128, checkpoint: u128| {
+            let delta = growth.wrapping_sub(checkpoint);
+            u64::try_from(
+                (delta >> 64) * amount as u128
+                    + (((delta & u64::MAX as u128) * amount as u128) >> 64),
+            )
+            .unwrap_or(u64::MAX)
+        };
+
+        (
+            earned(self.fee_growth_x, fee_growth_x),
+            earned(self.fee_growth_y, fee_growth_y),
+        )
+    }
+
+    /// Rejects instructions that cannot check an allowlist entry on a
+    /// permissioned pool.
+    pub fn check_permissionless(&self) -> Result<()> {
+        require!(!self.permissioned, AMMError::NotAllowlisted);
+        Ok(())
+    }
+
+    /// Price of x in y for `reserves`, as a Q64.64 value.
+    fn price(reserves: (u64, u64)) -> u128 {
+        ((reserves.1 as u128) << 64) / (reserves.0 as u128).max(1)
+    }
+
+    /// Rejects a swap taking reserves from `before` to `after` if it moves the
+    /// price more than `max_swap_impact`, or more than `max_slot_impact` from
+    /// the price at the start of `slot`.
+    pub fn check_price_impact(
+        &mut self,
+        before: (u64, u64),
+        after: (u64, u64),
+        slot: u64,
+    ) -> Result<()> {
+        let price_before = Self::price(before);
+        if self.slot != slot {
+            self.slot = slot;
+            self.slot_price = price_before;
+        }
+
+        let price_after = Self::price(after);
+        let impact = |from: u128| {
+            price_after
+                .abs_diff(from)
+                .saturating_mul(MAX_FEE_BPS as u128)
+                / from.max(1)
+        };
+
+        require!(
+            self.max_swap_impact == 0 || impact(price_before) <= self.max_swap_impact as u128,
+            AMMError::PriceImpactExceeded
+        );
+        require!(
+            self.max_slot_impact == 0 || impact(self.slot_price) <= self.max_slot_impact as u128,
+            AMMError::PriceImpactExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Accumulates the relative price move, in basis points, of a swap that
+    /// took reserves from `before` to `after`.
+    pub fn record_price_move(&mut self, before: (u64, u64), after: (u64, u64)) {
+        if !self.dynamic_fee {
+            return;
+        }
+
+        let (x0, y0) = before;
+        let (x1, y1) = after;
+        let price_after = (y1 as u128).saturating_mul(x0 as u128);
+        let price_before = (y0 as u128).saturating_mul(x1 as u128);
+        let move_bps = price_after
+            .abs_diff(price_before)
+            .saturating_mul(MAX_FEE_BPS as u128)
+            / price_before.max(1);
+
+        self.volatility = self
+            .volatility
+            .saturating_add(u64::try_from(move_bps).unwrap_or(u64::MAX))
+            .min(MAX_FEE_BPS as u64 * VOLATILITY_FEE_DIVISOR);
+    }
 }
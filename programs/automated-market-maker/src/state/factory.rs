@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Factory {
+    pub pool_count: u64,
+    pub bump: u8,
+}
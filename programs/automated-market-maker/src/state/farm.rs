@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::AMMError, MAX_FARM_REWARDS};
+
+/// Emission schedule paying `reward_per_second` of `mint` to LP stakers
+/// between `start_time` and `end_time`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RewardStream {
+    pub mint: Pubkey,
+    /// Account that funded the schedule.
+    pub funder: Pubkey,
+    pub reward_per_second: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Rewards per staked LP token, as a Q64.64 value.
+    pub reward_per_share: u128,
+}
+
+/// Staking pool for a pool's LP mint with up to `MAX_FARM_REWARDS` streams.
+#[account]
+#[derive(InitSpace)]
+pub struct Farm {
+    pub config: Pubkey,
+    pub mint_lp: Pubkey,
+    pub last_update: i64,
+    pub total_staked: u64,
+    pub bump: u8,
+    #[max_len(MAX_FARM_REWARDS)]
+    pub rewards: Vec<RewardStream>,
+}
+
+impl Farm {
+    /// Accrues emissions of every stream since the last update. Emissions
+    /// while nothing is staked stay in the reward vaults.
+    pub fn update(&mut self, now: i64) -> Result<()> {
+        if self.total_staked > 0 {
+            for stream in self.rewards.iter_mut() {
+                let from = self.last_update.max(stream.start_time);
+                let to = now.min(stream.end_time);
+
+                if to <= from {
+                    continue;
+                }
+
+                let rewards = ((to - from) as u128)
+                    .checked_mul(stream.reward_per_second as u128)
+                    .ok_or(AMMError::MathOverflow)?;
+
+                stream.reward_per_share = rewards
+                    .checked_shl(64)
+                    .filter(|v| v >> 64 == rewards)
+                    .map(|v| v / self.total_staked as u128)
+                    .and_then(|v| stream.reward_per_share.checked_add(v))
+                    .ok_or(AMMError::MathOverflow)?;
+            }
+        }
+
+        self.last_update = self.last_update.max(now);
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct StakeReward {
+    /// Stream `reward_per_share` at the last settlement.
+    pub reward_per_share: u128,
+    pub owed: u64,
+}
+
+/// A user's staked LP balance in a farm.
+#[account]
+#[derive(InitSpace)]
+pub struct FarmStake {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+    /// Per-stream settlement state, indexed like `Farm::rewards`.
+    #[max_len(MAX_FARM_REWARDS)]
+    pub rewards: Vec<StakeReward>,
+}
+
+impl FarmStake {
+    /// Moves rewards accrued since the last settlement into each stream's
+    /// `owed`. Must run after `Farm::update` and before `amount` changes.
+    pub fn settle(&mut self, farm: &Farm) -> Result<()> {
+        // streams added since the last settlement accrued from zero
+        self.rewards.resize(
+            farm.rewards.len(),
+            StakeReward {
+                reward_per_share: 0,
+                owed: 0,
+            },
+        );
+
+        for (reward, stream) in self.rewards.iter_mut().zip(farm.rewards.iter()) {
+            let pending = stream
+                .reward_per_share
+                .checked_sub(reward.reward_per_share)
+                .and_then(|v| v.checked_mul(self.amount as u128))
+                .map(|v| v >> 64)
+                .and_then(|v| u64::try_from(v).ok())
+                .ok_or(AMMError::MathOverflow)?;
+
+            reward.owed = reward
+                .owed
+                .checked_add(pending)
+                .ok_or(AMMError::MathOverflow)?;
+            reward.reward_per_share = stream.reward_per_share;
+        }
+
+        Ok(())
+    }
+}
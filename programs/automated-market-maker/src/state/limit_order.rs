@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Resting order selling `amount` of x (or y) for at least `min_out` of the
+/// other mint. Input and proceeds are escrowed in the order's token accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitOrder {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub id: u64,
+    pub is_x: bool,
+    pub amount: u64,
+    pub min_out: u64,
+    pub filled: bool,
+    pub bump: u8,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::Config;
+
+/// Receipt for LP tokens escrowed until `unlock_at`, or forever when
+/// `permanent`.
+#[account]
+#[derive(InitSpace)]
+pub struct LpLock {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub permanent: bool,
+    /// Pool fee growth when fees were last claimed.
+    pub fee_growth_x: u128,
+    pub fee_growth_y: u128,
+    pub bump: u8,
+}
+
+impl LpLock {
+    /// Fees earned by the locked LP tokens since the last claim.
+    pub fn earned(&self, config: &Config) -> (u64, u64) {
+        config.fees_earned(self.amount, self.fee_growth_x, self.fee_growth_y)
+    }
+}
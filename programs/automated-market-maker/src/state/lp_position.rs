@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::Config;
+
+/// Optional per-user record of LP principal and the fees earned on it.
+#[account]
+#[derive(InitSpace)]
+pub struct LpPosition {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    /// LP tokens deposited while the position was tracked.
+    pub lp_amount: u64,
+    pub cost_x: u64,
+    pub cost_y: u64,
+    pub fee_growth_x: u128,
+    pub fee_growth_y: u128,
+    /// Fees settled at the last checkpoint.
+    pub fees_x: u64,
+    pub fees_y: u64,
+    pub bump: u8,
+}
+
+impl LpPosition {
+    /// Fees earned since the checkpoint, which are still embedded in the
+    /// pool's reserves.
+    fn pending(&self, config: &Config) -> (u64, u64) {
+        config.fees_earned(self.lp_amount, self.fee_growth_x, self.fee_growth_y)
+    }
+
+    /// Total fees earned by the position.
+    pub fn earned(&self, config: &Config) -> (u64, u64) {
+        let (x, y) = self.pending(config);
+
+        (self.fees_x.saturating_add(x), self.fees_y.saturating_add(y))
+    }
+
+    /// Moves pending fees into the settled totals and checkpoints the pool's
+    /// fee growth. Must run before `lp_amount` changes.
+    pub fn settle(&mut self, config: &Config) {
+        (self.fees_x, self.fees_y) = self.earned(config);
+        self.fee_growth_x = config.fee_growth_x;
+        self.fee_growth_y = config.fee_growth_y;
+    }
+}
@@ -1,3 +1,23 @@
+pub mod allowlist_entry;
 pub mod config;
+pub mod factory;
+pub mod farm;
+pub mod limit_order;
+pub mod lp_lock;
+pub mod lp_position;
+pub mod pair;
+pub mod program_config;
+pub mod swap_record;
+pub mod twamm;
 
+pub use allowlist_entry::*;
 pub use config::*;
+pub use factory::*;
+pub use farm::*;
+pub use limit_order::*;
+pub use lp_lock::*;
+pub use lp_position::*;
+pub use pair::*;
+pub use program_config::*;
+pub use swap_record::*;
+pub use twamm::*;
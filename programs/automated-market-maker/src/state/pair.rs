@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Canonical pool registry entry for a mint pair and fee tier, keyed by the
+/// mints in ascending order.
+#[account]
+#[derive(InitSpace)]
+pub struct Pair {
+    pub config: Pubkey,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub fee: u16,
+    pub bump: u8,
+}
+
+impl Pair {
+    /// Seeds of two mints in ascending order, so a pair has one entry
+    /// whichever mint its pool prices as x.
+    pub fn sorted<'a>(mint_a: &'a Pubkey, mint_b: &'a Pubkey) -> [&'a [u8]; 2] {
+        match mint_a < mint_b {
+            true => [mint_a.as_ref(), mint_b.as_ref()],
+            false => [mint_b.as_ref(), mint_a.as_ref()],
+        }
+    }
+}
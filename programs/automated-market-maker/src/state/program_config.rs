@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::MAX_FEE_TIERS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct FeeTier {
+    pub fee: u16,
+    pub enabled: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub protocol_fee: u16,
+    pub bump: u8,
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+impl ProgramConfig {
+    pub fn is_fee_tier_enabled(&self, fee: u16) -> bool {
+        self.fee_tiers
+            .iter()
+            .any(|tier| tier.fee == fee && tier.enabled)
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Direction of a signer's last swap in a pool, used by the sandwich guard.
+#[account]
+#[derive(InitSpace)]
+pub struct SwapRecord {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub slot: u64,
+    pub is_x: bool,
+    pub bump: u8,
+}
@@ -0,0 +1,368 @@
+use anchor_lang::prelude::*;
+use constant_product_curve::{ConstantProduct, LiquidityPair};
+
+use crate::{error::AMMError, Config, MAX_TWAMM_EXPIRIES, PAUSE_SWAP};
+
+/// Sale rates ending at `end_time`, and the earnings per rate at that time
+/// once it has been executed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TwammExpiry {
+    pub end_time: i64,
+    pub sale_rate_x: u64,
+    pub sale_rate_y: u64,
+    pub earnings_per_rate_x: u128,
+    pub earnings_per_rate_y: u128,
+    /// Orders still open against this expiry.
+    pub orders: u32,
+}
+
+/// Long-term orders of a pool, selling `sale_rate_x` of x and `sale_rate_y`
+/// of y per second. Unsold input and proceeds are escrowed in the TWAMM's
+/// token accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct Twamm {
+    pub config: Pubkey,
+    pub last_executed: i64,
+    pub sale_rate_x: u64,
+    pub sale_rate_y: u64,
+    /// Proceeds in y per unit of x sale rate, as a Q64.64 value.
+    pub earnings_per_rate_x: u128,
+    /// Proceeds in x per unit of y sale rate, as a Q64.64 value.
+    pub earnings_per_rate_y: u128,
+    pub bump: u8,
+    /// Sorted by `end_time`.
+    #[max_len(MAX_TWAMM_EXPIRIES)]
+    pub expiries: Vec<TwammExpiry>,
+}
+
+/// Tokens moved between the TWAMM escrow and the pool vaults by an execution.
+#[derive(Default)]
+pub struct VirtualSettlement {
+    pub x_in: u64,
+    pub y_in: u64,
+    pub x_out: u64,
+    pub y_out: u64,
+}
+
+/// A user's sale of `sale_rate` per second of x (or y) until `end_time`.
+#[account]
+#[derive(InitSpace)]
+pub struct LongTermOrder {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub id: u64,
+    pub is_x: bool,
+    pub sale_rate: u64,
+    pub end_time: i64,
+    /// Earnings per rate of the order's side at the last withdrawal.
+    pub earnings_per_rate: u128,
+    pub bump: u8,
+}
+
+/// Earnings per rate at an executed expiry, moved out of `Twamm::expiries` so
+/// the slot can be reused while orders against it are still open.
+#[account]
+#[derive(InitSpace)]
+pub struct ArchivedExpiry {
+    pub twamm: Pubkey,
+    pub end_time: i64,
+    pub earnings_per_rate_x: u128,
+    pub earnings_per_rate_y: u128,
+    pub bump: u8,
+}
+
+fn per_rate(amount: u128, rate: u64) -> Result<u128> {
+    amount
+        .checked_shl(64)
+        .filter(|v| v >> 64 == amount)
+        .map(|v| v / rate as u128)
+        .ok_or(AMMError::MathOverflow.into())
+}
+
+impl Twamm {
+    /// Executes virtual orders up to `now` against the pool, one segment per
+    /// expiry crossed. Within a segment both sides are matched at the pool
+    /// price and only the excess is swapped through the curve. Nothing runs
+    /// while swaps are paused or the pool is empty.
+    pub fn execute(
+        &mut self,
+        config: &mut Config,
+        vault_x: u64,
+        vault_y: u64,
+        supply: u64,
+        now: i64,
+    ) -> Result<VirtualSettlement> {
+        let mut settlement = VirtualSettlement::default();
+
+        if config.invariant(PAUSE_SWAP).is_err() || vault_x == 0 || config.reserve_y(vault_y)? == 0
+        {
+            return Ok(settlement);
+        }
+
+        let mut reserves = (vault_x, vault_y);
+
+        loop {
+            let expiry = self
+                .expiries
+                .iter()
+                .position(|e| e.end_time > self.last_executed && e.end_time <= now);
+            let until = expiry.map(|i| self.expiries[i].end_time).unwrap_or(now);
+
+            if until > self.last_executed {
+                self.execute_segment(
+                    config,
+                    &mut reserves,
+                    &mut settlement,
+                    supply,
+                    (until - self.last_executed) as u128,
+                    now,
+                )?;
+                self.last_executed = until;
+            }
+
+            let Some(i) = expiry else { break };
+            let expiry = &mut self.expiries[i];
+            expiry.earnings_per_rate_x = self.earnings_per_rate_x;
+            expiry.earnings_per_rate_y = self.earnings_per_rate_y;
+            self.sale_rate_x = self
+                .sale_rate_x
+                .checked_sub(expiry.sale_rate_x)
+                .ok_or(AMMError::MathOverflow)?;
+            self.sale_rate_y = self
+                .sale_rate_y
+                .checked_sub(expiry.sale_rate_y)
+                .ok_or(AMMError::MathOverflow)?;
+        }
+
+        Ok(settlement)
+    }
+
+    fn execute_segment(
+        &mut self,
+        config: &mut Config,
+        reserves: &mut (u64, u64),
+        settlement: &mut VirtualSettlement,
+        supply: u64,
+        duration: u128,
+        now: i64,
+    ) -> Result<()> {
+        let sold_x = duration * self.sale_rate_x as u128;
+        let sold_y = duration * self.sale_rate_y as u128;
+
+        if sold_x == 0 && sold_y == 0 {
+            return Ok(());
+        }
+
+        let reserve_x = reserves.0;
+        let reserve_y = config.reserve_y(reserves.1)?;
+        let (rx, ry) = (reserve_x as u128, reserve_y as u128);
+        let value_x = sold_x.checked_mul(ry).ok_or(AMMError::MathOverflow)?;
+        let value_y = sold_y.checked_mul(rx).ok_or(AMMError::MathOverflow)?;
+
+        // the side worth more at the pool price swaps its excess
+        let is_x = value_x >= value_y;
+        let (matched, excess) = match is_x {
+            true => (value_y / ry, sold_x - value_y / ry),
+            false => (value_x / rx, sold_y - value_x / rx),
+        };
+        let excess = u64::try_from(excess).map_err(|_| AMMError::MathOverflow)?;
+
+        let mut out = 0;
+        if excess > 0 {
+            let fee = config.swap_fee(now);
+            let mut curve = ConstantProduct::init(reserve_x, reserve_y, supply, fee, None).unwrap();
+
+            let p = match is_x {
+                true => LiquidityPair::X,
+                false => LiquidityPair::Y,
+            };
+
+            let res = curve.swap(p, excess, 0).unwrap();
+
+            if is_x {
+                require_gte!(reserves.1, res.withdraw, AMMError::InsufficientLiquidity);
+            }
+
+            let reserves_after = match is_x {
+                true => (reserve_x + res.deposit, reserve_y - res.withdraw),
+                false => (reserve_x - res.withdraw, reserve_y + res.deposit),
+            };
+            config.record_price_move((reserve_x, reserve_y), reserves_after);
+            config.accrue_fee(is_x, res.fee, supply);
+
+            match is_x {
+                true => {
+                    reserves.0 += res.deposit;
+                    reserves.1 -= res.withdraw;
+                    settlement.x_in += res.deposit;
+                    settlement.y_out += res.withdraw;
+                }
+                false => {
+                    reserves.0 -= res.withdraw;
+                    reserves.1 += res.deposit;
+                    settlement.y_in += res.deposit;
+                    settlement.x_out += res.withdraw;
+                }
+            }
+            out = res.withdraw as u128;
+        }
+
+        // x sellers receive the y sold against them plus any swap output
+        let (earned_x, earned_y) = match is_x {
+            true => (sold_y + out, matched),
+            false => (matched, sold_x + out),
+        };
+
+        if self.sale_rate_x > 0 {
+            self.earnings_per_rate_x = per_rate(earned_x, self.sale_rate_x).and_then(|v| {
+                self.earnings_per_rate_x
+                    .checked_add(v)
+                    .ok_or(AMMError::MathOverflow.into())
+            })?;
+        }
+        if self.sale_rate_y > 0 {
+            self.earnings_per_rate_y = per_rate(earned_y, self.sale_rate_y).and_then(|v| {
+                self.earnings_per_rate_y
+                    .checked_add(v)
+                    .ok_or(AMMError::MathOverflow.into())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Earnings per rate of one side, as of the earlier of `end_time` and the
+    /// last execution. Executed expiries that were archived are read from
+    /// `archived`.
+    pub fn earnings_per_rate(
+        &self,
+        is_x: bool,
+        end_time: i64,
+        archived: Option<&ArchivedExpiry>,
+    ) -> Result<u128> {
+        let (earnings_x, earnings_y) = match end_time <= self.last_executed {
+            true => match self.expiries.iter().find(|e| e.end_time == end_time) {
+                Some(e) => (e.earnings_per_rate_x, e.earnings_per_rate_y),
+                None => archived
+                    .map(|a| (a.earnings_per_rate_x, a.earnings_per_rate_y))
+                    .ok_or(AMMError::MissingArchivedExpiry)?,
+            },
+            false => (self.earnings_per_rate_x, self.earnings_per_rate_y),
+        };
+
+        Ok(match is_x {
+            true => earnings_x,
+            false => earnings_y,
+        })
+    }
+
+    /// Removes an executed expiry so the slot can be reused, returning it to
+    /// be archived for the orders still open against it.
+    pub fn archive_expiry(&mut self, end_time: i64) -> Result<TwammExpiry> {
+        require_gte!(self.last_executed, end_time, AMMError::InvalidOrderExpiry);
+
+        let i = self
+            .expiries
+            .binary_search_by_key(&end_time, |e| e.end_time)
+            .map_err(|_| AMMError::InvalidOrderExpiry)?;
+
+        Ok(self.expiries.remove(i))
+    }
+
+    /// Adds a sale rate running from the last execution until `end_time`.
+    pub fn add_order(&mut self, is_x: bool, sale_rate: u64, end_time: i64) -> Result<()> {
+        let i = match self
+            .expiries
+            .binary_search_by_key(&end_time, |e| e.end_time)
+        {
+            Ok(i) => i,
+            Err(i) => {
+                require_gt!(
+                    MAX_TWAMM_EXPIRIES,
+                    self.expiries.len(),
+                    AMMError::TwammExpiriesFull
+                );
+                self.expiries.insert(
+                    i,
+                    TwammExpiry {
+                        end_time,
+                        sale_rate_x: 0,
+                        sale_rate_y: 0,
+                        earnings_per_rate_x: 0,
+                        earnings_per_rate_y: 0,
+                        orders: 0,
+                    },
+                );
+                i
+            }
+        };
+
+        let expiry = &mut self.expiries[i];
+        let (total, ending) = match is_x {
+            true => (&mut self.sale_rate_x, &mut expiry.sale_rate_x),
+            false => (&mut self.sale_rate_y, &mut expiry.sale_rate_y),
+        };
+        *total = total.checked_add(sale_rate).ok_or(AMMError::MathOverflow)?;
+        *ending = ending
+            .checked_add(sale_rate)
+            .ok_or(AMMError::MathOverflow)?;
+        expiry.orders += 1;
+
+        Ok(())
+    }
+
+    /// Removes a closed order, stopping its sale rate if it is still running.
+    pub fn remove_order(&mut self, is_x: bool, sale_rate: u64, end_time: i64) -> Result<()> {
+        let i = match self
+            .expiries
+            .binary_search_by_key(&end_time, |e| e.end_time)
+        {
+            Ok(i) => i,
+            // an archived expiry has no rates or orders left to update
+            Err(_) if end_time <= self.last_executed => return Ok(()),
+            Err(_) => return err!(AMMError::InvalidOrderExpiry),
+        };
+
+        let active = end_time > self.last_executed;
+        let expiry = &mut self.expiries[i];
+
+        if active {
+            let (total, ending) = match is_x {
+                true => (&mut self.sale_rate_x, &mut expiry.sale_rate_x),
+                false => (&mut self.sale_rate_y, &mut expiry.sale_rate_y),
+            };
+            *total = total.checked_sub(sale_rate).ok_or(AMMError::MathOverflow)?;
+            *ending = ending
+                .checked_sub(sale_rate)
+                .ok_or(AMMError::MathOverflow)?;
+        }
+
+        expiry.orders -= 1;
+        if expiry.orders == 0 {
+            self.expiries.remove(i);
+        }
+
+        Ok(())
+    }
+}
+
+impl LongTermOrder {
+    /// Proceeds earned since the last withdrawal. `Twamm::execute` must run
+    /// first.
+    pub fn proceeds(&self, twamm: &Twamm, archived: Option<&ArchivedExpiry>) -> Result<u64> {
+        twamm
+            .earnings_per_rate(self.is_x, self.end_time, archived)?
+            .checked_sub(self.earnings_per_rate)
+            .and_then(|v| v.checked_mul(self.sale_rate as u128))
+            .map(|v| v >> 64)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(AMMError::MathOverflow.into())
+    }
+
+    /// Input not yet sold, refunded on cancellation.
+    pub fn unsold(&self, twamm: &Twamm) -> u64 {
+        let remaining = (self.end_time - twamm.last_executed).max(0) as u64;
+        remaining.saturating_mul(self.sale_rate)
+    }
+}
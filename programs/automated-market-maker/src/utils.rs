@@ -0,0 +1,163 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    token::spl_token,
+    token_2022::spl_token_2022,
+    token_interface::{
+        close_account, sync_native, transfer_checked, CloseAccount, Mint, SyncNative, TokenAccount,
+        TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{error::AMMError, Config, Twamm, CONFIG_SEED, TWAMM_SEED};
+
+/// Whether `mint` is the wrapped SOL mint of either token program.
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Moves `amount` lamports from `owner` into its native token account and
+/// syncs the token balance.
+pub fn wrap_sol<'info>(
+    system_program: &Program<'info, System>,
+    token_program: &Interface<'info, TokenInterface>,
+    owner: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: owner,
+                to: account.clone(),
+            },
+        ),
+        amount,
+    )?;
+
+    sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        SyncNative { account },
+    ))
+}
+
+/// Closes `owner`'s native token account, returning its lamports to `owner`.
+pub fn unwrap_sol<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    owner: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+) -> Result<()> {
+    close_account(CpiContext::new(
+        token_program.to_account_info(),
+        CloseAccount {
+            account,
+            destination: owner.clone(),
+            authority: owner,
+        },
+    ))
+}
+
+/// Executes the TWAMM's virtual orders up to now and moves the net amounts
+/// between its escrow and the pool vaults, which are reloaded afterwards.
+pub fn settle_virtual_orders<'info>(
+    config: &mut Account<'info, Config>,
+    twamm: &mut Account<'info, Twamm>,
+    supply: u64,
+    mints: [&InterfaceAccount<'info, Mint>; 2],
+    vaults: [&mut InterfaceAccount<'info, TokenAccount>; 2],
+    escrows: [&InterfaceAccount<'info, TokenAccount>; 2],
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let settlement = twamm.execute(
+        config,
+        vaults[0].amount,
+        vaults[1].amount,
+        supply,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let config_seeds: &[&[&[u8]]] = &[&[CONFIG_SEED, &config.seed.to_le_bytes(), &[config.bump]]];
+    let twamm_seeds: &[&[&[u8]]] = &[&[TWAMM_SEED, twamm.config.as_ref(), &[twamm.bump]]];
+
+    let flows = [
+        (settlement.x_in, settlement.x_out),
+        (settlement.y_in, settlement.y_out),
+    ];
+
+    for (i, (amount_in, amount_out)) in flows.into_iter().enumerate() {
+        let (authority, from, to, amount, signer_seeds) = match amount_in >= amount_out {
+            true => (
+                twamm.to_account_info(),
+                escrows[i].to_account_info(),
+                vaults[i].to_account_info(),
+                amount_in - amount_out,
+                twamm_seeds,
+            ),
+            false => (
+                config.to_account_info(),
+                vaults[i].to_account_info(),
+                escrows[i].to_account_info(),
+                amount_out - amount_in,
+                config_seeds,
+            ),
+        };
+
+        if amount == 0 {
+            continue;
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    authority,
+                    from,
+                    to,
+                    mint: mints[i].to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mints[i].decimals,
+        )?;
+    }
+
+    for vault in vaults {
+        vault.reload()?;
+    }
+
+    Ok(())
+}
+
+/// Executes long-term orders before the pool is priced, once a TWAMM exists.
+/// Its accounts are optional on pool instructions but required from then on.
+pub fn settle_twamm<'info>(
+    config: &mut Account<'info, Config>,
+    twamm: Option<&mut Account<'info, Twamm>>,
+    escrows: [Option<&InterfaceAccount<'info, TokenAccount>>; 2],
+    supply: u64,
+    mints: [&InterfaceAccount<'info, Mint>; 2],
+    vaults: [&mut InterfaceAccount<'info, TokenAccount>; 2],
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if !config.twamm {
+        return Ok(());
+    }
+
+    let (Some(twamm), [Some(escrow_x), Some(escrow_y)]) = (twamm, escrows) else {
+        return err!(AMMError::MissingTwammAccounts);
+    };
+
+    settle_virtual_orders(
+        config,
+        twamm,
+        supply,
+        mints,
+        vaults,
+        [escrow_x, escrow_y],
+        token_program,
+    )
+}
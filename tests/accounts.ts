@@ -8,3 +8,94 @@ export async function fetchConfigAcc(
 ) {
   return await program.account.config.fetchNullable(configPda);
 }
+
+export async function fetchFactoryAcc(
+  program: Program<AutomatedMarketMaker>,
+  factoryPda: PublicKey,
+) {
+  return await program.account.factory.fetchNullable(factoryPda);
+}
+
+export async function fetchPairAcc(
+  program: Program<AutomatedMarketMaker>,
+  pairPda: PublicKey,
+) {
+  return await program.account.pair.fetchNullable(pairPda);
+}
+
+export async function fetchProgramConfigAcc(
+  program: Program<AutomatedMarketMaker>,
+  programConfigPda: PublicKey,
+) {
+  return await program.account.programConfig.fetchNullable(programConfigPda);
+}
+
+export async function fetchPositionAcc(
+  program: Program<AutomatedMarketMaker>,
+  positionPda: PublicKey,
+) {
+  return await program.account.lpPosition.fetchNullable(positionPda);
+}
+
+export async function fetchAllowlistAcc(
+  program: Program<AutomatedMarketMaker>,
+  allowlistPda: PublicKey,
+) {
+  return await program.account.allowlistEntry.fetchNullable(allowlistPda);
+}
+
+export async function fetchSwapRecordAcc(
+  program: Program<AutomatedMarketMaker>,
+  swapRecordPda: PublicKey,
+) {
+  return await program.account.swapRecord.fetchNullable(swapRecordPda);
+}
+
+export async function fetchFarmAcc(
+  program: Program<AutomatedMarketMaker>,
+  farmPda: PublicKey,
+) {
+  return await program.account.farm.fetchNullable(farmPda);
+}
+
+export async function fetchStakeAcc(
+  program: Program<AutomatedMarketMaker>,
+  stakePda: PublicKey,
+) {
+  return await program.account.farmStake.fetchNullable(stakePda);
+}
+
+export async function fetchLockAcc(
+  program: Program<AutomatedMarketMaker>,
+  lockPda: PublicKey,
+) {
+  return await program.account.lpLock.fetchNullable(lockPda);
+}
+
+export async function fetchOrderAcc(
+  program: Program<AutomatedMarketMaker>,
+  orderPda: PublicKey,
+) {
+  return await program.account.limitOrder.fetchNullable(orderPda);
+}
+
+export async function fetchTwammAcc(
+  program: Program<AutomatedMarketMaker>,
+  twammPda: PublicKey,
+) {
+  return await program.account.twamm.fetchNullable(twammPda);
+}
+
+export async function fetchLongTermOrderAcc(
+  program: Program<AutomatedMarketMaker>,
+  orderPda: PublicKey,
+) {
+  return await program.account.longTermOrder.fetchNullable(orderPda);
+}
+
+export async function fetchArchivedExpiryAcc(
+  program: Program<AutomatedMarketMaker>,
+  archivedExpiryPda: PublicKey,
+) {
+  return await program.account.archivedExpiry.fetchNullable(archivedExpiryPda);
+}
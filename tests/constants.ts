@@ -1,3 +1,20 @@
 import { Keypair } from "@solana/web3.js";
 
-export const [mintX, mintY] = Array.from({ length: 2 }, Keypair.generate);
+// sorted so pair registry keys list mint X first
+export const [mintX, mintY, mintZ] = Array.from(
+  { length: 3 },
+  Keypair.generate,
+).sort((a, b) =>
+  Buffer.compare(a.publicKey.toBuffer(), b.publicKey.toBuffer()),
+);
+
+export const FEE_TIERS = [30, 100, 200];
+
+export const PAUSE_ALL = 1;
+export const PAUSE_SWAP = 2;
+export const PAUSE_DEPOSIT = 4;
+export const PAUSE_WITHDRAW = 8;
+
+export const SANDWICH_GUARD_OFF = 0;
+export const SANDWICH_GUARD_SURCHARGE = 1;
+export const SANDWICH_GUARD_REJECT = 2;
@@ -0,0 +1,237 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getAllowlistPda, getConfigPda, getPairPda } from "../pda";
+import { fetchAllowlistAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("allowlist", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user, allowlistAuthority] = Array.from(
+    { length: 3 },
+    Keypair.generate,
+  );
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const allowlistPda = getAllowlistPda(configPda, user.publicKey);
+  const balance = 100_000;
+  const liquidity = 50_000;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user, allowlistAuthority].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 30,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 30),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .updateConfig({
+        paused: null,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: true,
+        allowlistAuthority: allowlistAuthority.publicKey,
+        referralFee: null,
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  async function addToAllowlist(signer: Keypair) {
+    await program.methods
+      .addToAllowlist()
+      .accountsPartial({
+        allowlistAuthority: signer.publicKey,
+        referralFee: null,
+        config: configPda,
+        user: user.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function swap(amount: number, allowlisted: boolean) {
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(amount),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        allowlistEntry: allowlisted ? allowlistPda : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("allowlisted wallet can swap", async () => {
+    await addToAllowlist(allowlistAuthority);
+
+    const allowlistAcc = await fetchAllowlistAcc(program, allowlistPda);
+
+    expect(allowlistAcc.config).toStrictEqual(configPda);
+    expect(allowlistAcc.user).toStrictEqual(user.publicKey);
+
+    await swap(100, true);
+  });
+
+  test("throws if swapping without an allowlist entry", async () => {
+    try {
+      await swap(100, false);
+    } catch (err) {
+      expectAnchorError(err, "NotAllowlisted");
+    }
+  });
+
+  test("throws if depositing after the entry is revoked", async () => {
+    await addToAllowlist(allowlistAuthority);
+
+    await program.methods
+      .removeFromAllowlist()
+      .accountsPartial({
+        allowlistAuthority: allowlistAuthority.publicKey,
+        referralFee: null,
+        config: configPda,
+        allowlistEntry: allowlistPda,
+      })
+      .signers([allowlistAuthority])
+      .rpc();
+
+    expect(await fetchAllowlistAcc(program, allowlistPda)).toBeNull();
+
+    try {
+      await program.methods
+        .deposit({
+          amount: new BN(100),
+          maxX: new BN(100),
+          maxY: new BN(100),
+          wrapSol: false,
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "NotAllowlisted");
+    }
+  });
+
+  test("throws if signer is not the allowlist authority", async () => {
+    try {
+      await addToAllowlist(admin);
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+});
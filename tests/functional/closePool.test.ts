@@ -0,0 +1,206 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { fetchPairAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("closePool", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const pairPda = getPairPda(mintX.publicKey, mintY.publicKey, 100);
+  const [vaultXPda, vaultYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      configPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup(
+      [admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+    ));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  test("close an empty pool", async () => {
+    const lamportsBefore = litesvm.getBalance(admin.publicKey);
+
+    await program.methods
+      .closePool()
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+        pair: pairPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    for (const pubkey of [configPda, vaultXPda, vaultYPda, pairPda]) {
+      expect(litesvm.getAccount(pubkey)).toBeNull();
+    }
+
+    expect(litesvm.getBalance(admin.publicKey)).toBeGreaterThan(
+      lamportsBefore,
+    );
+  });
+
+  test("frees the pair for a new pool", async () => {
+    await program.methods
+      .closePool()
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+        pair: pairPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    const newSeed = new BN(randomBytes(8));
+
+    await program.methods
+      .initialize({
+        seed: newSeed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    const pairAcc = await fetchPairAcc(program, pairPda);
+
+    expect(pairAcc.config).toStrictEqual(getConfigPda(newSeed));
+  });
+
+  test("throws if vaults are not empty", async () => {
+    const vaultXData = Buffer.alloc(ACCOUNT_SIZE);
+
+    AccountLayout.encode(
+      {
+        amount: 1n,
+        closeAuthority: PublicKey.default,
+        closeAuthorityOption: 0,
+        delegate: PublicKey.default,
+        delegateOption: 0,
+        delegatedAmount: 0n,
+        isNative: 0n,
+        isNativeOption: 0,
+        mint: mintX.publicKey,
+        owner: configPda,
+        state: 1,
+      },
+      vaultXData,
+    );
+
+    litesvm.setAccount(vaultXPda, {
+      data: vaultXData,
+      executable: false,
+      lamports: LAMPORTS_PER_SOL,
+      owner: TOKEN_PROGRAM_ID,
+    });
+
+    try {
+      await program.methods
+        .closePool()
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configPda,
+          pair: pairPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "PoolNotEmpty");
+    }
+  });
+
+  test("throws if signer is not config authority", async () => {
+    try {
+      await program.methods
+        .closePool()
+        .accountsPartial({
+          authority: user.publicKey,
+          config: configPda,
+          pair: pairPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+
+  test("throws if pair is omitted for a registered pool", async () => {
+    try {
+      await program.methods
+        .closePool()
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configPda,
+          pair: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingPairAccount");
+    }
+  });
+});
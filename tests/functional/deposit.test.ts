@@ -3,7 +3,7 @@ import { AutomatedMarketMaker } from "../../target/types/automated_market_maker"
 import { BN, Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { randomBytes } from "crypto";
-import { mintX, mintY } from "../constants";
+import { mintX, mintY, PAUSE_DEPOSIT } from "../constants";
 import {
   ACCOUNT_SIZE,
   AccountLayout,
@@ -11,7 +11,7 @@ import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { getConfigPda, getMintLpPda } from "../pda";
+import { getConfigPda, getMintLpPda, getPairPda } from "../pda";
 import { LiteSVM } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
 import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
@@ -105,13 +105,18 @@ describe("deposit", () => {
     await program.methods
       .initialize({
         seed,
-        locked: false,
+        paused: 0,
         fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
       })
       .accounts({
         authority: admin.publicKey,
         mintX: mintX.publicKey,
         mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([admin])
@@ -127,6 +132,7 @@ describe("deposit", () => {
         amount: new BN(amount),
         maxX: new BN(amount * (1 + slippage)),
         maxY: new BN(amount * (1 + slippage)),
+        wrapSol: false,
       })
       .accountsPartial({
         user: user.publicKey,
@@ -176,12 +182,21 @@ describe("deposit", () => {
     expect(Number(userAtaLpBal)).toEqual(amount);
   });
 
-  test("throws if depositing into a locked pool", async () => {
+  test("throws if depositing into a pool with deposits paused", async () => {
     await program.methods
       .updateConfig({
-        locked: true,
+        paused: PAUSE_DEPOSIT,
         fee: null,
+        flashFee: null,
         authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
       })
       .accountsPartial({
         authority: admin.publicKey,
@@ -199,6 +214,7 @@ describe("deposit", () => {
           amount: new BN(amount),
           maxX: new BN(amount * (1 + slippage)),
           maxY: new BN(amount * (1 + slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
@@ -208,7 +224,7 @@ describe("deposit", () => {
         .signers([user])
         .rpc();
     } catch (err) {
-      expectAnchorError(err, "PoolLocked");
+      expectAnchorError(err, "OperationPaused");
     }
   });
 
@@ -222,6 +238,7 @@ describe("deposit", () => {
           amount: new BN(amount),
           maxX: new BN(amount * (1 + slippage)),
           maxY: new BN(amount * (1 + slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
@@ -0,0 +1,127 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY, PAUSE_SWAP } from "../constants";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("emergencyPause", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [authority, guardian] = Array.from({ length: 2 }, Keypair.generate);
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup(
+      [authority, guardian].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+    ));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .updateConfig({
+        paused: null,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: guardian.publicKey,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
+      })
+      .accountsPartial({
+        authority: authority.publicKey,
+        config: configPda,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  test("pause a pool as guardian", async () => {
+    await program.methods
+      .emergencyPause({
+        paused: PAUSE_SWAP,
+      })
+      .accountsPartial({
+        guardian: guardian.publicKey,
+        config: configPda,
+      })
+      .signers([guardian])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.guardian).toStrictEqual(guardian.publicKey);
+    expect(configAcc.paused).toEqual(PAUSE_SWAP);
+  });
+
+  test("throws if signer is not config guardian", async () => {
+    try {
+      await program.methods
+        .emergencyPause({
+          paused: PAUSE_SWAP,
+        })
+        .accountsPartial({
+          guardian: authority.publicKey,
+          config: configPda,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigGuardian");
+    }
+  });
+
+  test("throws if guardian tries to unpause", async () => {
+    try {
+      await program.methods
+        .emergencyPause({
+          paused: 0,
+        })
+        .accountsPartial({
+          guardian: guardian.publicKey,
+          config: configPda,
+        })
+        .signers([guardian])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPauseFlags");
+    }
+  });
+});
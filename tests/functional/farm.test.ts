@@ -0,0 +1,410 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY, mintZ } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  getConfigPda,
+  getFarmPda,
+  getMintLpPda,
+  getPairPda,
+  getStakePda,
+} from "../pda";
+import { fetchFarmAcc, fetchStakeAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("farm", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const tokenAccounts = [
+    [mintX, user],
+    [mintY, user],
+    [mintZ, admin],
+    [mintZ, user],
+  ].map(([mint, owner]) => ({
+    mint: mint.publicKey,
+    owner: owner.publicKey,
+    pubkey: getAssociatedTokenAddressSync(
+      mint.publicKey,
+      owner.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    ),
+  }));
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const mintLp = getMintLpPda(configPda);
+  const farmPda = getFarmPda(configPda);
+  const stakePda = getStakePda(farmPda, user.publicKey);
+  const userAtaLpPda = getAssociatedTokenAddressSync(
+    mintLp,
+    user.publicKey,
+    false,
+    TOKEN_PROGRAM_ID,
+  );
+  const [userAtaXPda, userAtaZPda] = [mintX, mintZ].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const amount = 1_000;
+  const rewardPerSecond = 10;
+  const duration = 100;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...tokenAccounts.map(({ mint, owner, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: 10_000n,
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(amount),
+        maxX: new BN(amount),
+        maxY: new BN(amount),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    await program.methods
+      .createFarm()
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .addReward({
+        rewardPerSecond: new BN(rewardPerSecond),
+        startTime: new BN(now),
+        endTime: new BN(now + duration),
+      })
+      .accountsPartial({
+        funder: admin.publicKey,
+        authority: admin.publicKey,
+        config: configPda,
+        farm: farmPda,
+        rewardMint: mintZ.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .stake({
+        amount: new BN(amount),
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        farm: farmPda,
+        mintLp,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  function warp(seconds: number) {
+    const clock = litesvm.getClock();
+    clock.unixTimestamp += BigInt(seconds);
+    litesvm.setClock(clock);
+  }
+
+  test("stake LP tokens into a farm", async () => {
+    const farmAcc = await fetchFarmAcc(program, farmPda);
+    const stakeAcc = await fetchStakeAcc(program, stakePda);
+    const userAtaLpBal = (await getAccount(provider.connection, userAtaLpPda))
+      .amount;
+
+    expect(farmAcc.totalStaked.toNumber()).toEqual(amount);
+    expect(farmAcc.rewards[0].mint).toStrictEqual(mintZ.publicKey);
+    expect(farmAcc.rewards[0].funder).toStrictEqual(admin.publicKey);
+    expect(stakeAcc.amount.toNumber()).toEqual(amount);
+    expect(stakeAcc.owner).toStrictEqual(user.publicKey);
+    expect(Number(userAtaLpBal)).toEqual(0);
+  });
+
+  function rewardAccounts(mints: PublicKey[]) {
+    return mints.flatMap((mint) => [
+      { pubkey: mint, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          mint,
+          farmPda,
+          true,
+          TOKEN_PROGRAM_ID,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          mint,
+          user.publicKey,
+          false,
+          TOKEN_PROGRAM_ID,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+    ]);
+  }
+
+  test("claim rewards accrued over time", async () => {
+    warp(duration / 2);
+
+    await program.methods
+      .claimAll()
+      .accountsPartial({
+        user: user.publicKey,
+        farm: farmPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(rewardAccounts([mintZ.publicKey]))
+      .signers([user])
+      .rpc();
+
+    const userAtaZBal = (await getAccount(provider.connection, userAtaZPda))
+      .amount;
+    const earned = Number(userAtaZBal) - 10_000;
+
+    // the only staker earns every emission, less rounding
+    expect(earned).toBeGreaterThanOrEqual((rewardPerSecond * duration) / 2 - 1);
+    expect(earned).toBeLessThanOrEqual((rewardPerSecond * duration) / 2);
+  });
+
+  test("claim rewards from a single stream", async () => {
+    warp(duration / 2);
+
+    await program.methods
+      .claimRewards()
+      .accountsPartial({
+        user: user.publicKey,
+        farm: farmPda,
+        rewardMint: mintZ.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userAtaZBal = (await getAccount(provider.connection, userAtaZPda))
+      .amount;
+    const earned = Number(userAtaZBal) - 10_000;
+
+    expect(earned).toBeGreaterThanOrEqual((rewardPerSecond * duration) / 2 - 1);
+    expect(earned).toBeLessThanOrEqual((rewardPerSecond * duration) / 2);
+  });
+
+  test("claim from multiple reward streams", async () => {
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    // a partner co-incentivizes the pool with its own token
+    await program.methods
+      .addReward({
+        rewardPerSecond: new BN(rewardPerSecond),
+        startTime: new BN(now),
+        endTime: new BN(now + duration),
+      })
+      .accountsPartial({
+        funder: user.publicKey,
+        authority: admin.publicKey,
+        config: configPda,
+        farm: farmPda,
+        rewardMint: mintX.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, admin])
+      .rpc();
+
+    warp(duration / 2);
+
+    const initUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    await program.methods
+      .claimAll()
+      .accountsPartial({
+        user: user.publicKey,
+        farm: farmPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(rewardAccounts([mintZ.publicKey, mintX.publicKey]))
+      .signers([user])
+      .rpc();
+
+    const userAtaZBal = (await getAccount(provider.connection, userAtaZPda))
+      .amount;
+    const userAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    expect(Number(userAtaZBal)).toBeGreaterThan(10_000);
+    expect(Number(userAtaXBal - initUserAtaXBal)).toBeGreaterThanOrEqual(
+      (rewardPerSecond * duration) / 2 - 1,
+    );
+  });
+
+  test("throws if reward mint already has a stream", async () => {
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    try {
+      await program.methods
+        .addReward({
+          rewardPerSecond: new BN(rewardPerSecond),
+          startTime: new BN(now),
+          endTime: new BN(now + duration),
+        })
+        .accountsPartial({
+          funder: admin.publicKey,
+          authority: admin.publicKey,
+          config: configPda,
+          farm: farmPda,
+          rewardMint: mintZ.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "DuplicateRewardMint");
+    }
+  });
+
+  test("throws if reward accounts do not cover every stream", async () => {
+    try {
+      await program.methods
+        .claimAll()
+        .accountsPartial({
+          user: user.publicKey,
+          farm: farmPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidRewardAccounts");
+    }
+  });
+
+  test("unstake LP tokens from a farm", async () => {
+    await program.methods
+      .unstake({
+        amount: new BN(amount),
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        farm: farmPda,
+        mintLp,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const stakeAcc = await fetchStakeAcc(program, stakePda);
+    const userAtaLpBal = (await getAccount(provider.connection, userAtaLpPda))
+      .amount;
+
+    expect(stakeAcc.amount.toNumber()).toEqual(0);
+    expect(Number(userAtaLpBal)).toEqual(amount);
+  });
+
+  test("throws if unstaking more than staked", async () => {
+    try {
+      await program.methods
+        .unstake({
+          amount: new BN(amount + 1),
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          farm: farmPda,
+          mintLp,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InsufficientStake");
+    }
+  });
+});
@@ -0,0 +1,205 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY, mintZ } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("feeDiscount", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda, userAtaZPda] = [mintX, mintY, mintZ].map(
+    (mint) => {
+      return getAssociatedTokenAddressSync(
+        mint.publicKey,
+        user.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+      );
+    },
+  );
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const balance = 100_000;
+  const liquidity = 50_000;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+        { mint: mintZ.publicKey, pubkey: userAtaZPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .setFeeDiscount({
+        mint: mintZ.publicKey,
+        tiers: [
+          { minBalance: new BN(1_000), fee: 50 },
+          { minBalance: new BN(10_000), fee: 0 },
+          { minBalance: new BN(0), fee: 0 },
+          { minBalance: new BN(0), fee: 0 },
+        ],
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  async function swap(discountAccount: PublicKey | null) {
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(1_000),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        discountAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("holders swap at the discounted fee", async () => {
+    // the 100_000 balance clears the zero-fee tier
+    await swap(userAtaZPda);
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.discountMint).toStrictEqual(mintZ.publicKey);
+    expect(configAcc.discountTiers[1].minBalance.toNumber()).toBe(10_000);
+    expect(configAcc.feeGrowthX.toString()).toBe("0");
+  });
+
+  test("swaps without a discount account pay the pool fee", async () => {
+    await swap(null);
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.feeGrowthX.toString()).not.toBe("0");
+  });
+
+  test("throws if discount account is for another mint", async () => {
+    try {
+      await swap(userAtaXPda);
+    } catch (err) {
+      expectAnchorError(err, "InvalidDiscountAccount");
+    }
+  });
+
+  test("throws if tiers are not ascending", async () => {
+    try {
+      await program.methods
+        .setFeeDiscount({
+          mint: mintZ.publicKey,
+          tiers: [
+            { minBalance: new BN(10_000), fee: 0 },
+            { minBalance: new BN(1_000), fee: 50 },
+            { minBalance: new BN(0), fee: 0 },
+            { minBalance: new BN(0), fee: 0 },
+          ],
+        })
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configPda,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidDiscountTiers");
+    }
+  });
+});
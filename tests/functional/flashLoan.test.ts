@@ -0,0 +1,235 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  Transaction,
+} from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getMintLpPda, getPairPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("flashLoan", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const vaultXPda = getAssociatedTokenAddressSync(
+    mintX.publicKey,
+    configPda,
+    true,
+    TOKEN_PROGRAM_ID,
+  );
+
+  const flashFee = 100;
+
+  beforeEach(async () => {
+    const [userAtaXData, userAtaYData] = Array.from({ length: 2 }, () =>
+      Buffer.alloc(ACCOUNT_SIZE),
+    );
+
+    [
+      [userAtaXData, mintX],
+      [userAtaYData, mintY],
+    ].forEach(([data, mint]: [Buffer, Keypair]) => {
+      AccountLayout.encode(
+        {
+          amount: 10n,
+          closeAuthority: PublicKey.default,
+          closeAuthorityOption: 0,
+          delegate: PublicKey.default,
+          delegateOption: 0,
+          delegatedAmount: 0n,
+          isNative: 0n,
+          isNativeOption: 0,
+          mint: mint.publicKey,
+          owner: user.publicKey,
+          state: 1,
+        },
+        data,
+      );
+    });
+
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        [userAtaXPda, userAtaXData],
+        [userAtaYPda, userAtaYData],
+      ].map(([pubkey, data]: [PublicKey, Buffer]) => ({
+        pubkey,
+        account: {
+          data,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      })),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(5),
+        maxX: new BN(5),
+        maxY: new BN(5),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  async function flashBorrowIx(amount: number) {
+    return await program.methods
+      .flashBorrow({
+        isX: true,
+        amount: new BN(amount),
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  async function flashRepayIx() {
+    return await program.methods
+      .flashRepay()
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        protocol: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+  }
+
+  test("borrow and repay a flash loan", async () => {
+    const initVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+    const initUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    const amount = 2;
+    const fee = Math.ceil((amount * flashFee) / 10_000);
+
+    await provider.sendAndConfirm(
+      new Transaction().add(await flashBorrowIx(amount), await flashRepayIx()),
+      [user],
+    );
+
+    const postVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+    const postUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    expect(Number(postVaultXBal - initVaultXBal)).toEqual(fee);
+    expect(Number(initUserAtaXBal - postUserAtaXBal)).toEqual(fee);
+
+    // the fee is owed to LPs, so it accrues to the loan mint's fee growth
+    const configAcc = await fetchConfigAcc(program, configPda);
+    const supply = (
+      await getMint(provider.connection, getMintLpPda(configPda))
+    ).supply;
+
+    expect(BigInt(configAcc.feeGrowthX.toString())).toEqual(
+      (BigInt(fee) << 64n) / supply,
+    );
+    expect(configAcc.feeGrowthY.toString()).toBe("0");
+  });
+
+  test("throws if flash borrow is not repaid", async () => {
+    try {
+      await provider.sendAndConfirm(
+        new Transaction().add(await flashBorrowIx(2)),
+        [user],
+      );
+    } catch (err) {
+      expectAnchorError(err, "MissingFlashRepay");
+    }
+  });
+
+  test("throws if swapping during a flash loan", async () => {
+    const swapIx = await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(1),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .instruction();
+
+    try {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          await flashBorrowIx(2),
+          swapIx,
+          await flashRepayIx(),
+        ),
+        [user],
+      );
+    } catch (err) {
+      expectAnchorError(err, "FlashLoanActive");
+    }
+  });
+});
@@ -0,0 +1,163 @@
+import { beforeEach, describe, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("flashSwap", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+
+  beforeEach(async () => {
+    const [userAtaXData, userAtaYData] = Array.from({ length: 2 }, () =>
+      Buffer.alloc(ACCOUNT_SIZE),
+    );
+
+    [
+      [userAtaXData, mintX],
+      [userAtaYData, mintY],
+    ].forEach(([data, mint]: [Buffer, Keypair]) => {
+      AccountLayout.encode(
+        {
+          amount: 10n,
+          closeAuthority: PublicKey.default,
+          closeAuthorityOption: 0,
+          delegate: PublicKey.default,
+          delegateOption: 0,
+          delegatedAmount: 0n,
+          isNative: 0n,
+          isNativeOption: 0,
+          mint: mint.publicKey,
+          owner: user.publicKey,
+          state: 1,
+        },
+        data,
+      );
+    });
+
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        [userAtaXPda, userAtaXData],
+        [userAtaYPda, userAtaYData],
+      ].map(([pubkey, data]: [PublicKey, Buffer]) => ({
+        pubkey,
+        account: {
+          data,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      })),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(5),
+        maxX: new BN(5),
+        maxY: new BN(5),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  test("throws if callback program is this program", async () => {
+    try {
+      await program.methods
+        .flashSwap({
+          isX: true,
+          amountOut: new BN(1),
+          maxIn: new BN(5),
+          data: Buffer.alloc(0),
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          callbackProgram: program.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidCallbackProgram");
+    }
+  });
+
+  test("throws if required input exceeds maximum", async () => {
+    try {
+      await program.methods
+        .flashSwap({
+          isX: true,
+          amountOut: new BN(2),
+          maxIn: new BN(1),
+          data: Buffer.alloc(0),
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          callbackProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "SlippageExceeded");
+    }
+  });
+});
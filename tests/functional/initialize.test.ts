@@ -8,23 +8,29 @@ import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { getConfigPda, getMintLpPda } from "../pda";
-import { fetchConfigAcc } from "../accounts";
+import {
+  getConfigPda,
+  getFactoryPda,
+  getMintLpPda,
+  getPairPda,
+} from "../pda";
+import { fetchConfigAcc, fetchFactoryAcc, fetchPairAcc } from "../accounts";
 import { LiteSVM } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
-import { fundedSystemAccountInfo, getSetup } from "../setup";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
 
 describe("initialize", () => {
-  let { litesvm, provider, program } = {} as {
+  let { litesvm, provider, program, programAdmin } = {} as {
     litesvm: LiteSVM;
     provider: LiteSVMProvider;
     program: Program<AutomatedMarketMaker>;
+    programAdmin: Keypair;
   };
 
   const authority = Keypair.generate();
 
   beforeEach(async () => {
-    ({ litesvm, provider, program } = await getSetup([
+    ({ litesvm, provider, program, programAdmin } = await getSetup([
       {
         pubkey: authority.publicKey,
         account: fundedSystemAccountInfo(),
@@ -34,19 +40,38 @@ describe("initialize", () => {
 
   test("initialize a pool config", async () => {
     const seed = new BN(randomBytes(8));
-    const locked = false;
+    const paused = 0;
     const fee = 100;
+    const virtualY = new BN(1_000);
+    const flashFee = 9;
+    const protocolFee = 2_000;
+
+    await program.methods
+      .updateProgramConfig({
+        protocolFee,
+        admin: null,
+      })
+      .accounts({
+        admin: programAdmin.publicKey,
+      })
+      .signers([programAdmin])
+      .rpc();
 
     await program.methods
       .initialize({
         seed,
-        locked,
+        paused,
         fee,
+        virtualY,
+        flashFee,
+        dynamicFee: false,
+        maxFee: 0,
       })
       .accounts({
         authority: authority.publicKey,
         mintX: mintX.publicKey,
         mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, fee),
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([authority])
@@ -57,9 +82,13 @@ describe("initialize", () => {
     const configAcc = await fetchConfigAcc(program, configPda);
     const mintLpPda = getMintLpPda(configPda);
 
+    expect(configAcc.version).toEqual(2);
     expect(configAcc.seed).toStrictEqual(seed);
-    expect(configAcc.locked).toEqual(locked);
+    expect(configAcc.paused).toEqual(paused);
     expect(configAcc.fee).toEqual(fee);
+    expect(configAcc.virtualY).toStrictEqual(virtualY);
+    expect(configAcc.flashFee).toEqual(flashFee);
+    expect(configAcc.protocolFee).toEqual(protocolFee);
     expect(configAcc.mintX).toStrictEqual(mintX.publicKey);
     expect(configAcc.mintY).toStrictEqual(mintY.publicKey);
     expect(configAcc.authority).toStrictEqual(authority.publicKey);
@@ -86,5 +115,163 @@ describe("initialize", () => {
 
     expect(vaultXAcc).not.toBeNull();
     expect(vaultYAcc).not.toBeNull();
+
+    const pairAcc = await fetchPairAcc(
+      program,
+      getPairPda(mintX.publicKey, mintY.publicKey, fee),
+    );
+
+    expect(pairAcc.config).toStrictEqual(configPda);
+    expect(pairAcc.mintX).toStrictEqual(mintX.publicKey);
+    expect(pairAcc.mintY).toStrictEqual(mintY.publicKey);
+    expect(pairAcc.fee).toEqual(fee);
+
+    const factoryAcc = await fetchFactoryAcc(program, getFactoryPda());
+
+    expect(factoryAcc.poolCount.toNumber()).toEqual(1);
+
+    const pool = await program.methods
+      .getPool({
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        fee,
+      })
+      .accounts({
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, fee),
+      })
+      .view();
+
+    expect(pool).toStrictEqual(configPda);
+  });
+
+  test("throws if pool already exists for pair and fee tier", async () => {
+    const fee = 100;
+
+    await program.methods
+      .initialize({
+        seed: new BN(randomBytes(8)),
+        paused: 0,
+        fee,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, fee),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    await expect(
+      program.methods
+        .initialize({
+          seed: new BN(randomBytes(8)),
+          paused: 0,
+          fee,
+          virtualY: new BN(0),
+          flashFee: 0,
+          dynamicFee: false,
+          maxFee: 0,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          pair: getPairPda(mintX.publicKey, mintY.publicKey, fee),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc(),
+    ).rejects.toThrow();
+  });
+
+  test("throws if fee tier is not enabled", async () => {
+    try {
+      await program.methods
+        .initialize({
+          seed: new BN(randomBytes(8)),
+          paused: 0,
+          fee: 42,
+          virtualY: new BN(0),
+          flashFee: 0,
+          dynamicFee: false,
+          maxFee: 0,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          pair: getPairPda(mintX.publicKey, mintY.publicKey, 42),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidFeeTier");
+    }
+  });
+
+  test("initialize a pool with the mints in either order", async () => {
+    const seed = new BN(randomBytes(8));
+    const fee = 100;
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee,
+        virtualY: new BN(1_000),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        mintX: mintY.publicKey,
+        mintY: mintX.publicKey,
+        pair: getPairPda(mintY.publicKey, mintX.publicKey, fee),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    const configPda = getConfigPda(seed);
+    const configAcc = await fetchConfigAcc(program, configPda);
+    const pairAcc = await fetchPairAcc(
+      program,
+      getPairPda(mintX.publicKey, mintY.publicKey, fee),
+    );
+
+    expect(configAcc.mintX).toStrictEqual(mintY.publicKey);
+    expect(configAcc.mintY).toStrictEqual(mintX.publicKey);
+    expect(pairAcc.config).toStrictEqual(configPda);
+
+    // a second pool for the same pair and fee tier collides in either order
+    await expect(
+      program.methods
+        .initialize({
+          seed: new BN(randomBytes(8)),
+          paused: 0,
+          fee,
+          virtualY: new BN(0),
+          flashFee: 0,
+          dynamicFee: false,
+          maxFee: 0,
+        })
+        .accounts({
+          authority: authority.publicKey,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          pair: getPairPda(mintX.publicKey, mintY.publicKey, fee),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc(),
+    ).rejects.toThrow();
   });
 });
@@ -0,0 +1,324 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getOrderPda, getPairPda } from "../pda";
+import { fetchConfigAcc, fetchOrderAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("limitOrder", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user, cranker] = Array.from({ length: 3 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const orderId = new BN(1);
+  const orderPda = getOrderPda(configPda, user.publicKey, orderId);
+  const [orderAtaXPda, orderAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      orderPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+  const orderAccounts = [orderPda, orderAtaXPda, orderAtaYPda].map(
+    (pubkey) => ({ pubkey, isSigner: false, isWritable: true }),
+  );
+
+  const liquidity = 1_000;
+  const orderAmount = 100;
+  // above the ~90 y that 100 x fetches at the initial 1:1 price
+  const minOut = 150;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user, cranker].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: 10_000n,
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .placeOrder({
+        id: orderId,
+        isX: true,
+        amount: new BN(orderAmount),
+        minOut: new BN(minOut),
+      })
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  async function fillOrders() {
+    await program.methods
+      .fillOrders()
+      .accountsPartial({
+        cranker: cranker.publicKey,
+        config: configPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(orderAccounts)
+      .signers([cranker])
+      .rpc();
+  }
+
+  async function swapYForX(amount: number) {
+    await program.methods
+      .swap({
+        isX: false,
+        amount: new BN(amount),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("place order escrows input", async () => {
+    const orderAcc = await fetchOrderAcc(program, orderPda);
+
+    expect(orderAcc.owner).toStrictEqual(user.publicKey);
+    expect(orderAcc.isX).toBe(true);
+    expect(orderAcc.amount.toNumber()).toBe(orderAmount);
+    expect(orderAcc.minOut.toNumber()).toBe(minOut);
+    expect(orderAcc.filled).toBe(false);
+
+    const orderAtaXBal = (await getAccount(provider.connection, orderAtaXPda))
+      .amount;
+    const userAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    expect(Number(orderAtaXBal)).toBe(orderAmount);
+    expect(Number(userAtaXBal)).toBe(10_000 - liquidity - orderAmount);
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.openOrders).toBe(1);
+  });
+
+  test("fill orders skips orders below their price", async () => {
+    await fillOrders();
+
+    const orderAcc = await fetchOrderAcc(program, orderPda);
+    const orderAtaXBal = (await getAccount(provider.connection, orderAtaXPda))
+      .amount;
+
+    expect(orderAcc.filled).toBe(false);
+    expect(Number(orderAtaXBal)).toBe(orderAmount);
+  });
+
+  test("fill orders fills once the price crosses", async () => {
+    await swapYForX(500);
+    await fillOrders();
+
+    const orderAcc = await fetchOrderAcc(program, orderPda);
+    const orderAtaXBal = (await getAccount(provider.connection, orderAtaXPda))
+      .amount;
+    const orderAtaYBal = (await getAccount(provider.connection, orderAtaYPda))
+      .amount;
+
+    expect(orderAcc.filled).toBe(true);
+    expect(Number(orderAtaXBal)).toBe(0);
+    expect(Number(orderAtaYBal)).toBeGreaterThanOrEqual(minOut);
+  });
+
+  test("cancel order collects proceeds and closes the order", async () => {
+    await swapYForX(500);
+    await fillOrders();
+
+    const orderAtaYBal = (await getAccount(provider.connection, orderAtaYPda))
+      .amount;
+    const preUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+
+    await program.methods
+      .cancelOrder()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        order: orderPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const postUserAtaYBal = (
+      await getAccount(provider.connection, userAtaYPda)
+    ).amount;
+
+    expect(postUserAtaYBal - preUserAtaYBal).toBe(orderAtaYBal);
+    expect(await fetchOrderAcc(program, orderPda)).toBeNull();
+    expect(litesvm.getAccount(orderAtaXPda)).toBeNull();
+    expect(litesvm.getAccount(orderAtaYPda)).toBeNull();
+  });
+
+  test("cancel open order returns input", async () => {
+    await program.methods
+      .cancelOrder()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        order: orderPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    expect(Number(userAtaXBal)).toBe(10_000 - liquidity);
+    expect(await fetchOrderAcc(program, orderPda)).toBeNull();
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.openOrders).toBe(0);
+  });
+
+  test("throws if closing a pool with open orders", async () => {
+    try {
+      await program.methods
+        .closePool()
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configPda,
+          pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "OpenOrders");
+    }
+  });
+
+  test("fill orders rejects incomplete order accounts", async () => {
+    try {
+      await program.methods
+        .fillOrders()
+        .accountsPartial({
+          cranker: cranker.publicKey,
+          config: configPda,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(orderAccounts.slice(0, 2))
+        .signers([cranker])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOrderAccounts");
+    }
+  });
+});
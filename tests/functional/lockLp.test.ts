@@ -0,0 +1,331 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getLockPda, getMintLpPda, getPairPda } from "../pda";
+import { fetchConfigAcc, fetchLockAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("lockLp", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const mintLp = getMintLpPda(configPda);
+  const userAtaLpPda = getAssociatedTokenAddressSync(
+    mintLp,
+    user.publicKey,
+    false,
+    TOKEN_PROGRAM_ID,
+  );
+  const lockId = new BN(1);
+  const lockPda = getLockPda(configPda, user.publicKey, lockId);
+  const amount = 1_000;
+  const duration = 3_600;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: 10_000n,
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(amount),
+        maxX: new BN(amount),
+        maxY: new BN(amount),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    await program.methods
+      .lockLp({
+        id: lockId,
+        amount: new BN(amount),
+        unlockAt: new BN(now + duration),
+        permanent: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  test("lock LP tokens", async () => {
+    const lockAcc = await fetchLockAcc(program, lockPda);
+    const userAtaLpBal = (await getAccount(provider.connection, userAtaLpPda))
+      .amount;
+
+    expect(lockAcc.owner).toStrictEqual(user.publicKey);
+    expect(lockAcc.amount.toNumber()).toEqual(amount);
+    expect(Number(userAtaLpBal)).toEqual(0);
+  });
+
+  test("unlock LP tokens after the unlock time", async () => {
+    const clock = litesvm.getClock();
+    clock.unixTimestamp += BigInt(duration);
+    litesvm.setClock(clock);
+
+    await program.methods
+      .unlockLp()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        lock: lockPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userAtaLpBal = (await getAccount(provider.connection, userAtaLpPda))
+      .amount;
+
+    expect(Number(userAtaLpBal)).toEqual(amount);
+    expect(await fetchLockAcc(program, lockPda)).toBeNull();
+  });
+
+  test("throws if unlocking before the unlock time", async () => {
+    try {
+      await program.methods
+        .unlockLp()
+        .accountsPartial({
+          owner: user.publicKey,
+          config: configPda,
+          lock: lockPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "LockActive");
+    }
+  });
+
+  async function lockPermanently(id: BN) {
+    await program.methods
+      .deposit({
+        amount: new BN(amount),
+        maxX: new BN(amount),
+        maxY: new BN(amount),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .lockLp({
+        id,
+        amount: new BN(amount),
+        unlockAt: new BN(0),
+        permanent: true,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    return getLockPda(configPda, user.publicKey, id);
+  }
+
+  test("claim fees from a permanent lock", async () => {
+    const permanentLockPda = await lockPermanently(new BN(2));
+
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(amount),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const [vaultXPda, vaultYPda] = [mintX, mintY].map((mint) =>
+      getAssociatedTokenAddressSync(
+        mint.publicKey,
+        configPda,
+        true,
+        TOKEN_PROGRAM_ID,
+      ),
+    );
+    const balance = async (pubkey: PublicKey) =>
+      (await getAccount(provider.connection, pubkey)).amount;
+
+    const [vaultX, vaultY, initUserX, initUserY] = await Promise.all(
+      [vaultXPda, vaultYPda, userAtaXPda, userAtaYPda].map(balance),
+    );
+    const supply = (await getMint(provider.connection, mintLp)).supply;
+    const configAcc = await fetchConfigAcc(program, configPda);
+    const initLockAcc = await fetchLockAcc(program, permanentLockPda);
+    const locked = BigInt(initLockAcc.amount.toString());
+
+    // LpLock::earned
+    const earned = (growth: BN, checkpoint: BN) =>
+      ((BigInt(growth.toString()) - BigInt(checkpoint.toString())) * locked) >>
+      64n;
+    const feeX = earned(configAcc.feeGrowthX, initLockAcc.feeGrowthX);
+    const feeY = earned(configAcc.feeGrowthY, initLockAcc.feeGrowthY);
+
+    expect(feeX).toBeGreaterThan(0n);
+
+    // the fees valued in x, over the x value of both reserves per LP token
+    const feeValue = feeX + (feeY * vaultX) / vaultY;
+    const reservesValue = vaultX + (vaultY * vaultX + vaultY - 1n) / vaultY;
+    const burned = (feeValue * supply) / reservesValue;
+
+    await program.methods
+      .claimLockedFees()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        lock: permanentLockPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const [postUserX, postUserY] = await Promise.all(
+      [userAtaXPda, userAtaYPda].map(balance),
+    );
+    const lockAcc = await fetchLockAcc(program, permanentLockPda);
+    const paidX = postUserX - initUserX;
+    const paidY = postUserY - initUserY;
+
+    expect(lockAcc.permanent).toBe(true);
+    expect(BigInt(lockAcc.amount.toString())).toEqual(locked - burned);
+    expect(paidX).toEqual((vaultX * burned) / supply);
+    expect(paidY).toEqual((vaultY * burned) / supply);
+    // only fees are withdrawn, never principal
+    expect(paidX + (paidY * vaultX) / vaultY).toBeLessThanOrEqual(
+      feeX + (feeY * vaultX) / vaultY,
+    );
+  });
+
+  test("throws if unlocking a permanent lock", async () => {
+    const permanentLockPda = await lockPermanently(new BN(2));
+
+    const clock = litesvm.getClock();
+    clock.unixTimestamp += BigInt(duration * 1_000);
+    litesvm.setClock(clock);
+
+    try {
+      await program.methods
+        .unlockLp()
+        .accountsPartial({
+          owner: user.publicKey,
+          config: configPda,
+          lock: permanentLockPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "LockActive");
+    }
+  });
+});
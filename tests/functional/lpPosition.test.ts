@@ -0,0 +1,221 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getMintLpPda, getPairPda, getPositionPda } from "../pda";
+import { fetchPositionAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("lpPosition", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const positionPda = getPositionPda(configPda, user.publicKey);
+  const amount = 1_000_000;
+
+  beforeEach(async () => {
+    const [userAtaXData, userAtaYData] = [mintX, mintY].map((mint) => {
+      const data = Buffer.alloc(ACCOUNT_SIZE);
+
+      AccountLayout.encode(
+        {
+          amount: BigInt(amount * 10),
+          closeAuthority: PublicKey.default,
+          closeAuthorityOption: 0,
+          delegate: PublicKey.default,
+          delegateOption: 0,
+          delegatedAmount: 0n,
+          isNative: 0n,
+          isNativeOption: 0,
+          mint: mint.publicKey,
+          owner: user.publicKey,
+          state: 1,
+        },
+        data,
+      );
+
+      return data;
+    });
+
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        [userAtaXPda, userAtaXData],
+        [userAtaYPda, userAtaYData],
+      ].map(([pubkey, data]) => ({
+        pubkey: pubkey as PublicKey,
+        account: {
+          data: data as Buffer,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      })),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .openPosition()
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(amount),
+        maxX: new BN(amount),
+        maxY: new BN(amount),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        position: positionPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  test("track cost basis and earned fees", async () => {
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(amount / 10),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const positionAcc = await fetchPositionAcc(program, positionPda);
+
+    expect(positionAcc.lpAmount.toNumber()).toEqual(amount);
+    expect(positionAcc.costX.toNumber()).toEqual(amount);
+    expect(positionAcc.costY.toNumber()).toEqual(amount);
+
+    const fees = await program.methods
+      .getPositionFees()
+      .accountsPartial({
+        config: configPda,
+        position: positionPda,
+      })
+      .view();
+
+    // the position holds the entire supply, so it earns the whole fee
+    expect(fees.feeX.toNumber()).toBeGreaterThan(0);
+    expect(fees.feeX.toNumber()).toBeLessThanOrEqual(amount / 10 / 100);
+    expect(fees.feeY.toNumber()).toEqual(0);
+  });
+
+  test("reduce cost basis on withdraw", async () => {
+    await program.methods
+      .withdraw({
+        amount: new BN(amount / 2),
+        minX: new BN(1),
+        minY: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        position: positionPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const positionAcc = await fetchPositionAcc(program, positionPda);
+
+    expect(positionAcc.lpAmount.toNumber()).toEqual(amount / 2);
+    expect(positionAcc.costX.toNumber()).toEqual(amount / 2);
+    expect(positionAcc.costY.toNumber()).toEqual(amount / 2);
+  });
+
+  test("throws if position does not belong to the recipient", async () => {
+    const recipient = Keypair.generate();
+    const recipientLpPda = getAssociatedTokenAddressSync(
+      getMintLpPda(configPda),
+      recipient.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+
+    try {
+      await program.methods
+        .deposit({
+          amount: new BN(amount),
+          maxX: new BN(amount),
+          maxY: new BN(amount),
+          wrapSol: false,
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          recipient: recipient.publicKey,
+          recipientLp: recipientLpPda,
+          position: positionPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidPositionOwner");
+    }
+  });
+});
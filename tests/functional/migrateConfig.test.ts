@@ -0,0 +1,165 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import idl from "../../target/idl/automated_market_maker.json";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY, PAUSE_ALL } from "../constants";
+import { getConfigPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+function configV0Data(seed: BN, locked: boolean, authority: PublicKey) {
+  const discriminator = idl.accounts.find(
+    (acc) => acc.name === "Config",
+  )!.discriminator;
+  const data = Buffer.alloc(8 + 8 + 1 + 1 + 1 + 2 + 32 * 3);
+
+  let offset = Buffer.from(discriminator).copy(data);
+  offset += seed.toArrayLike(Buffer, "le", 8).copy(data, offset);
+  offset = data.writeUInt8(locked ? 1 : 0, offset);
+  offset = data.writeUInt8(255, offset);
+  offset = data.writeUInt8(254, offset);
+  offset = data.writeUInt16LE(100, offset);
+  offset += mintX.publicKey.toBuffer().copy(data, offset);
+  offset += mintY.publicKey.toBuffer().copy(data, offset);
+  authority.toBuffer().copy(data, offset);
+
+  return data;
+}
+
+describe("migrateConfig", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [authorityA, authorityB] = Array.from({ length: 2 }, Keypair.generate);
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[authorityA, authorityB].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      {
+        pubkey: configPda,
+        account: {
+          lamports: LAMPORTS_PER_SOL / 100,
+          data: configV0Data(seed, true, authorityA.publicKey),
+          owner: new PublicKey(idl.address),
+          executable: false,
+        },
+      },
+    ]));
+  });
+
+  test("migrate a v0 config", async () => {
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: authorityA.publicKey,
+        config: configPda,
+      })
+      .signers([authorityA])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.version).toEqual(2);
+    expect(configAcc.seed).toStrictEqual(seed);
+    expect(configAcc.paused).toEqual(PAUSE_ALL);
+    expect(configAcc.bump).toEqual(255);
+    expect(configAcc.lpBump).toEqual(254);
+    expect(configAcc.fee).toEqual(100);
+    expect(configAcc.virtualY.toNumber()).toEqual(0);
+    expect(configAcc.dynamicFee).toBe(false);
+    expect(configAcc.mintX).toStrictEqual(mintX.publicKey);
+    expect(configAcc.mintY).toStrictEqual(mintY.publicKey);
+    expect(configAcc.authority).toStrictEqual(authorityA.publicKey);
+    expect(configAcc.guardian).toStrictEqual(PublicKey.default);
+  });
+
+  test("migrate a v1 config", async () => {
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: authorityA.publicKey,
+        config: configPda,
+      })
+      .signers([authorityA])
+      .rpc();
+
+    // v1 ended at the current layout's first sandwich guard field
+    const account = litesvm.getAccount(configPda);
+    const data = Buffer.from(account.data).subarray(0, 8 + 246);
+    data.writeUInt8(1, 8);
+
+    litesvm.setAccount(configPda, { ...account, data });
+    litesvm.expireBlockhash();
+
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: authorityA.publicKey,
+        config: configPda,
+      })
+      .signers([authorityA])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.version).toEqual(2);
+    expect(configAcc.seed).toStrictEqual(seed);
+    expect(configAcc.paused).toEqual(PAUSE_ALL);
+    expect(configAcc.authority).toStrictEqual(authorityA.publicKey);
+    expect(configAcc.sandwichGuard).toEqual(0);
+    expect(configAcc.sandwichFee).toEqual(0);
+  });
+
+  test("throws if config is already migrated", async () => {
+    await program.methods
+      .migrateConfig()
+      .accounts({
+        authority: authorityA.publicKey,
+        config: configPda,
+      })
+      .signers([authorityA])
+      .rpc();
+
+    litesvm.expireBlockhash();
+
+    try {
+      await program.methods
+        .migrateConfig()
+        .accounts({
+          authority: authorityA.publicKey,
+          config: configPda,
+        })
+        .signers([authorityA])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigVersion");
+    }
+  });
+
+  test("throws if signer is not config authority", async () => {
+    try {
+      await program.methods
+        .migrateConfig()
+        .accounts({
+          authority: authorityB.publicKey,
+          config: configPda,
+        })
+        .signers([authorityB])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidConfigAuthority");
+    }
+  });
+});
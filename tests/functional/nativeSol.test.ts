@@ -0,0 +1,179 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  MINT_SIZE,
+  MintLayout,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("nativeSol", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [poolMintX, poolMintY] = [NATIVE_MINT, mintY.publicKey].sort((a, b) =>
+    Buffer.compare(a.toBuffer(), b.toBuffer()),
+  );
+  const solIsX = poolMintX.equals(NATIVE_MINT);
+  const [userAtaSolPda, userAtaTokenPda] = [NATIVE_MINT, mintY.publicKey].map(
+    (mint) => {
+      return getAssociatedTokenAddressSync(
+        mint,
+        user.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+      );
+    },
+  );
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const amount = 1_000_000;
+
+  beforeEach(async () => {
+    const nativeMintData = Buffer.alloc(MINT_SIZE);
+
+    MintLayout.encode(
+      {
+        decimals: 9,
+        freezeAuthority: PublicKey.default,
+        freezeAuthorityOption: 0,
+        isInitialized: true,
+        mintAuthority: PublicKey.default,
+        mintAuthorityOption: 0,
+        supply: 0n,
+      },
+      nativeMintData,
+    );
+
+    const userAtaTokenData = Buffer.alloc(ACCOUNT_SIZE);
+
+    AccountLayout.encode(
+      {
+        amount: BigInt(amount * 10),
+        closeAuthority: PublicKey.default,
+        closeAuthorityOption: 0,
+        delegate: PublicKey.default,
+        delegateOption: 0,
+        delegatedAmount: 0n,
+        isNative: 0n,
+        isNativeOption: 0,
+        mint: mintY.publicKey,
+        owner: user.publicKey,
+        state: 1,
+      },
+      userAtaTokenData,
+    );
+
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      {
+        pubkey: NATIVE_MINT,
+        account: {
+          data: nativeMintData,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      },
+      {
+        pubkey: userAtaTokenPda,
+        account: {
+          data: userAtaTokenData,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      },
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: poolMintX,
+        mintY: poolMintY,
+        pair: getPairPda(poolMintX, poolMintY, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(amount),
+        maxX: new BN(amount),
+        maxY: new BN(amount),
+        wrapSol: true,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  test("deposit native SOL from lamports", async () => {
+    const vaultSolPda = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      configPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+    const vaultSolBal = (await getAccount(provider.connection, vaultSolPda))
+      .amount;
+
+    expect(Number(vaultSolBal)).toEqual(amount);
+  });
+
+  test("swap into native SOL and unwrap", async () => {
+    const initUserBal = litesvm.getBalance(user.publicKey);
+
+    await program.methods
+      .swap({
+        isX: !solIsX,
+        amount: new BN(amount / 10),
+        min: new BN(1),
+        wrapSol: true,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    expect(litesvm.getAccount(userAtaSolPda)).toBeNull();
+    expect(litesvm.getBalance(user.publicKey)).toBeGreaterThan(initUserBal);
+  });
+});
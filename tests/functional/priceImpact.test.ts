@@ -0,0 +1,195 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("priceImpact", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const balance = 100_000;
+  const liquidity = 50_000;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 30,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 30),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .updateConfig({
+        paused: null,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: null,
+        maxSwapImpact: 300,
+        maxSlotImpact: 300,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  async function swap(amount: number) {
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(amount),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("swap records the slot start price", async () => {
+    // ~2% price move
+    await swap(500);
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.maxSwapImpact).toBe(300);
+    expect(configAcc.maxSlotImpact).toBe(300);
+    expect(configAcc.slot.toString()).toBe(litesvm.getClock().slot.toString());
+    // 1:1 pool before the swap
+    expect(configAcc.slotPrice.toString()).toBe((1n << 64n).toString());
+  });
+
+  test("throws if a swap moves the price too far", async () => {
+    try {
+      // ~4% price move
+      await swap(1_000);
+    } catch (err) {
+      expectAnchorError(err, "PriceImpactExceeded");
+    }
+  });
+
+  test("throws if swaps in one slot move the price too far", async () => {
+    await swap(500);
+
+    try {
+      await swap(501);
+    } catch (err) {
+      expectAnchorError(err, "PriceImpactExceeded");
+    }
+
+    litesvm.warpToSlot(litesvm.getClock().slot + 1n);
+    await swap(502);
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.slot.toString()).toBe(litesvm.getClock().slot.toString());
+  });
+});
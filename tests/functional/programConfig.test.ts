@@ -0,0 +1,144 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { FEE_TIERS } from "../constants";
+import { getProgramConfigPda, getProgramDataPda } from "../pda";
+import { fetchProgramConfigAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("programConfig", () => {
+  let { litesvm, provider, program, programAdmin } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+    programAdmin: Keypair;
+  };
+
+  const nonAdmin = Keypair.generate();
+  const programConfigPda = getProgramConfigPda();
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program, programAdmin } = await getSetup([
+      {
+        pubkey: nonAdmin.publicKey,
+        account: fundedSystemAccountInfo(),
+      },
+    ]));
+  });
+
+  test("initialize program config", async () => {
+    const programConfigAcc = await fetchProgramConfigAcc(
+      program,
+      programConfigPda,
+    );
+
+    expect(programConfigAcc.admin).toStrictEqual(programAdmin.publicKey);
+    expect(programConfigAcc.feeTiers.map((tier) => tier.fee)).toEqual(
+      FEE_TIERS,
+    );
+    expect(programConfigAcc.feeTiers.every((tier) => tier.enabled)).toBe(true);
+  });
+
+  test("add and disable fee tiers", async () => {
+    const newFee = 500;
+    const disabledFee = FEE_TIERS[0];
+
+    for (const [fee, enabled] of [
+      [newFee, true],
+      [disabledFee, false],
+    ] as [number, boolean][]) {
+      await program.methods
+        .setFeeTier({
+          fee,
+          enabled,
+        })
+        .accounts({
+          admin: programAdmin.publicKey,
+        })
+        .signers([programAdmin])
+        .rpc();
+    }
+
+    const programConfigAcc = await fetchProgramConfigAcc(
+      program,
+      programConfigPda,
+    );
+
+    expect(
+      programConfigAcc.feeTiers.find((tier) => tier.fee === newFee).enabled,
+    ).toBe(true);
+    expect(
+      programConfigAcc.feeTiers.find((tier) => tier.fee === disabledFee)
+        .enabled,
+    ).toBe(false);
+  });
+
+  test("update program config", async () => {
+    const protocolFee = 1_500;
+
+    await program.methods
+      .updateProgramConfig({
+        protocolFee,
+        admin: nonAdmin.publicKey,
+      })
+      .accounts({
+        admin: programAdmin.publicKey,
+      })
+      .signers([programAdmin])
+      .rpc();
+
+    const programConfigAcc = await fetchProgramConfigAcc(
+      program,
+      programConfigPda,
+    );
+
+    expect(programConfigAcc.protocolFee).toEqual(protocolFee);
+    expect(programConfigAcc.admin).toStrictEqual(nonAdmin.publicKey);
+  });
+
+  test("throws if signer is not program admin", async () => {
+    try {
+      await program.methods
+        .setFeeTier({
+          fee: 500,
+          enabled: true,
+        })
+        .accounts({
+          admin: nonAdmin.publicKey,
+        })
+        .signers([nonAdmin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidProgramAdmin");
+    }
+  });
+
+  test("throws if signer is not upgrade authority", async () => {
+    // drop the config created during setup so it can be initialized again
+    litesvm.setAccount(programConfigPda, {
+      data: Buffer.alloc(0),
+      executable: false,
+      lamports: 0,
+      owner: SystemProgram.programId,
+    });
+
+    try {
+      await program.methods
+        .initializeProgramConfig({
+          protocolFee: 0,
+          feeTiers: FEE_TIERS,
+        })
+        .accounts({
+          admin: nonAdmin.publicKey,
+          programData: getProgramDataPda(),
+        })
+        .signers([nonAdmin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidProgramAdmin");
+    }
+  });
+});
@@ -0,0 +1,261 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getPairPda } from "../pda";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("referral", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user, referrer] = Array.from({ length: 3 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda, referrerAtaXPda, referrerAtaYPda] = [
+    user,
+    referrer,
+  ].flatMap((owner) =>
+    [mintX, mintY].map((mint) =>
+      getAssociatedTokenAddressSync(
+        mint.publicKey,
+        owner.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+      ),
+    ),
+  );
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const balance = 100_000;
+  const liquidity = 50_000;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+      ...[
+        { mint: mintX.publicKey, pubkey: referrerAtaXPda },
+        { mint: mintY.publicKey, pubkey: referrerAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: 0n,
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: referrer.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .updateConfig({
+        paused: null,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: 5000,
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  async function swap(referrerAccount: PublicKey | null) {
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(1_000),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        referrer: referrerAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("swap pays the referral share of the fee", async () => {
+    const vaultXPda = getAssociatedTokenAddressSync(
+      mintX.publicKey,
+      configPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+
+    const initVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+    const initUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    await swap(referrerAtaXPda);
+
+    const postVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+    const postUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+    const referrerAtaXBal = (
+      await getAccount(provider.connection, referrerAtaXPda)
+    ).amount;
+
+    // 1% of 1_000 is charged, half of which goes to the referrer
+    expect(Number(referrerAtaXBal)).toEqual(5);
+    expect(Number(initUserAtaXBal - postUserAtaXBal)).toEqual(
+      Number(postVaultXBal - initVaultXBal + referrerAtaXBal),
+    );
+  });
+
+  test("throws if referrer account is not for the input mint", async () => {
+    try {
+      await swap(referrerAtaYPda);
+    } catch (err) {
+      expectAnchorError(err, "InvalidReferrerAccount");
+    }
+  });
+
+  test("throws if referral fee exceeds the maximum", async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          paused: null,
+          fee: null,
+          flashFee: null,
+          authority: null,
+          guardian: null,
+          maxSwapImpact: null,
+          maxSlotImpact: null,
+          sandwichGuard: null,
+          sandwichFee: null,
+          permissioned: null,
+          allowlistAuthority: null,
+          referralFee: 5001,
+        })
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configPda,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidFee");
+    }
+  });
+});
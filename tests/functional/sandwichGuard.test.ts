@@ -0,0 +1,311 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import {
+  mintX,
+  mintY,
+  SANDWICH_GUARD_REJECT,
+  SANDWICH_GUARD_SURCHARGE,
+} from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  getConfigPda,
+  getMintLpPda,
+  getPairPda,
+  getSwapRecordPda,
+} from "../pda";
+import { fetchSwapRecordAcc } from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("sandwichGuard", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const swapRecordPda = getSwapRecordPda(configPda, user.publicKey);
+  const [vaultXPda, vaultYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      configPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+  const balance = 100_000;
+  const liquidity = 50_000;
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 30,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 30),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .openSwapRecord()
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  async function setGuard(sandwichGuard: number, sandwichFee: number) {
+    await program.methods
+      .updateConfig({
+        paused: null,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard,
+        sandwichFee,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function swap(isX: boolean, amount: number, swapRecord = true) {
+    await program.methods
+      .swap({
+        isX,
+        amount: new BN(amount),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        swapRecord: swapRecord ? swapRecordPda : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("swap records the direction", async () => {
+    await setGuard(SANDWICH_GUARD_REJECT, 0);
+    await swap(true, 1_000);
+
+    const swapRecordAcc = await fetchSwapRecordAcc(program, swapRecordPda);
+
+    expect(swapRecordAcc.owner).toStrictEqual(user.publicKey);
+    expect(swapRecordAcc.isX).toBe(true);
+    expect(swapRecordAcc.slot.toString()).toBe(
+      litesvm.getClock().slot.toString(),
+    );
+  });
+
+  test("throws if swap record is missing", async () => {
+    await setGuard(SANDWICH_GUARD_REJECT, 0);
+
+    try {
+      await swap(true, 1_000, false);
+    } catch (err) {
+      expectAnchorError(err, "InvalidSwapRecord");
+    }
+  });
+
+  test("throws on an opposite swap in the same slot", async () => {
+    await setGuard(SANDWICH_GUARD_REJECT, 0);
+    await swap(true, 1_000);
+
+    try {
+      await swap(false, 1_000);
+    } catch (err) {
+      expectAnchorError(err, "SandwichDetected");
+    }
+
+    litesvm.warpToSlot(litesvm.getClock().slot + 1n);
+    await swap(false, 1_001);
+  });
+
+  test("surcharges an opposite swap in the same slot", async () => {
+    await setGuard(SANDWICH_GUARD_SURCHARGE, 500);
+    await swap(true, 1_000);
+
+    const preUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    await swap(false, 1_000);
+
+    const postUserAtaXBal = (
+      await getAccount(provider.connection, userAtaXPda)
+    ).amount;
+
+    // ~1_016 x at the 0.3% fee, ~966 x with the 5% surcharge
+    expect(Number(postUserAtaXBal - preUserAtaXBal)).toBeGreaterThan(940);
+    expect(Number(postUserAtaXBal - preUserAtaXBal)).toBeLessThan(990);
+  });
+
+  test("throws if a route reverses direction in the same slot", async () => {
+    await setGuard(SANDWICH_GUARD_REJECT, 0);
+    await swap(true, 1_000);
+
+    try {
+      await program.methods
+        .swapRoute({
+          amount: new BN(1_000),
+          minOut: new BN(1),
+        })
+        .accounts({
+          user: user.publicKey,
+          mintIn: mintY.publicKey,
+          mintOut: mintX.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          [
+            configPda,
+            getMintLpPda(configPda),
+            vaultXPda,
+            vaultYPda,
+            mintX.publicKey,
+            mintY.publicKey,
+          ].map((pubkey, i) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: i === 0 || i === 2 || i === 3,
+          })),
+        )
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidSwapRecord");
+    }
+  });
+
+  test("throws on an opposite flash swap in the same slot", async () => {
+    await setGuard(SANDWICH_GUARD_REJECT, 0);
+    await swap(true, 1_000);
+
+    for (const swapRecord of [null, swapRecordPda]) {
+      try {
+        await program.methods
+          .flashSwap({
+            isX: false,
+            amountOut: new BN(1_000),
+            maxIn: new BN(2_000),
+            data: Buffer.alloc(0),
+          })
+          .accountsPartial({
+            user: user.publicKey,
+            config: configPda,
+            callbackProgram: TOKEN_PROGRAM_ID,
+            swapRecord,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+      } catch (err) {
+        expectAnchorError(
+          err,
+          swapRecord ? "SandwichDetected" : "InvalidSwapRecord",
+        );
+      }
+    }
+  });
+});
@@ -3,7 +3,7 @@ import { AutomatedMarketMaker } from "../../target/types/automated_market_maker"
 import { BN, Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { randomBytes } from "crypto";
-import { mintX, mintY } from "../constants";
+import { mintX, mintY, PAUSE_SWAP } from "../constants";
 import {
   ACCOUNT_SIZE,
   AccountLayout,
@@ -11,7 +11,8 @@ import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { getConfigPda } from "../pda";
+import { getConfigPda, getPairPda } from "../pda";
+import { fetchConfigAcc } from "../accounts";
 import { LiteSVM } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
 import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
@@ -104,13 +105,18 @@ describe("swap", () => {
     await program.methods
       .initialize({
         seed,
-        locked: false,
+        paused: 0,
         fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
       })
       .accounts({
         authority: admin.publicKey,
         mintX: mintX.publicKey,
         mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([admin])
@@ -124,6 +130,7 @@ describe("swap", () => {
         amount: new BN(amount),
         maxX: new BN(amount * (1 + slippage)),
         maxY: new BN(amount * (1 + slippage)),
+        wrapSol: false,
       })
       .accountsPartial({
         user: user.publicKey,
@@ -167,6 +174,7 @@ describe("swap", () => {
         isX: swapXForY,
         amount: new BN(amount),
         min: new BN(amount * (1 - slippage)),
+        wrapSol: false,
       })
       .accountsPartial({
         user: user.publicKey,
@@ -193,12 +201,199 @@ describe("swap", () => {
     );
   });
 
-  test("throws if swapping from a locked pool", async () => {
+  test("swap to a different recipient", async () => {
+    const recipient = Keypair.generate();
+    const recipientAtaYPda = getAssociatedTokenAddressSync(
+      mintY.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+
+    const initUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+
+    const amount = 2;
+    const slippage = 0.01;
+
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(amount),
+        min: new BN(amount * (1 - slippage)),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        recipient: recipient.publicKey,
+        recipientY: recipientAtaYPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const postUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+    const recipientAtaYBal = (
+      await getAccount(provider.connection, recipientAtaYPda)
+    ).amount;
+
+    expect(postUserAtaYBal).toEqual(initUserAtaYBal);
+    expect(Number(recipientAtaYBal)).toBeGreaterThan(0);
+  });
+
+  test("swap against virtual reserves", async () => {
+    const virtualSeed = new BN(randomBytes(8));
+    const virtualConfigPda = getConfigPda(virtualSeed);
+
+    await program.methods
+      .initialize({
+        seed: virtualSeed,
+        paused: 0,
+        fee: 30,
+        virtualY: new BN(5),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 30),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(5),
+        maxX: new BN(5),
+        maxY: new BN(0),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: virtualConfigPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const vaultYPda = getAssociatedTokenAddressSync(
+      mintY.publicKey,
+      virtualConfigPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+
+    expect(
+      Number((await getAccount(provider.connection, vaultYPda)).amount),
+    ).toEqual(0);
+
+    const initUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    await program.methods
+      .swap({
+        isX: false,
+        amount: new BN(2),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: virtualConfigPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const postUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+
+    expect(Number(postUserAtaXBal)).toBeGreaterThan(Number(initUserAtaXBal));
+    expect(
+      Number((await getAccount(provider.connection, vaultYPda)).amount),
+    ).toEqual(2);
+  });
+
+  test("swap with a dynamic fee", async () => {
+    const dynamicSeed = new BN(randomBytes(8));
+    const dynamicConfigPda = getConfigPda(dynamicSeed);
+
+    await program.methods
+      .initialize({
+        seed: dynamicSeed,
+        paused: 0,
+        fee: 200,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: true,
+        maxFee: 1_000,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 200),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(5),
+        maxX: new BN(5),
+        maxY: new BN(5),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: dynamicConfigPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .swap({
+        isX: true,
+        amount: new BN(2),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: dynamicConfigPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, dynamicConfigPda);
+
+    expect(configAcc.volatility.toNumber()).toBeGreaterThan(0);
+  });
+
+  test("throws if swapping from a pool with swaps paused", async () => {
     await program.methods
       .updateConfig({
-        locked: true,
+        paused: PAUSE_SWAP,
         fee: null,
+        flashFee: null,
         authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
       })
       .accountsPartial({
         authority: admin.publicKey,
@@ -217,6 +412,7 @@ describe("swap", () => {
           isX: swapXForY,
           amount: new BN(amount),
           min: new BN(amount * (1 - slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
@@ -226,7 +422,7 @@ describe("swap", () => {
         .signers([user])
         .rpc();
     } catch (err) {
-      expectAnchorError(err, "PoolLocked");
+      expectAnchorError(err, "OperationPaused");
     }
   });
 
@@ -241,6 +437,7 @@ describe("swap", () => {
           isX: swapXForY,
           amount: new BN(amount),
           min: new BN(amount * (1 - slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
@@ -0,0 +1,239 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY, mintZ } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { getConfigPda, getMintLpPda, getPairPda } from "../pda";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("swapRoute", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda, userAtaZPda] = [mintX, mintY, mintZ].map(
+    (mint) => {
+      return getAssociatedTokenAddressSync(
+        mint.publicKey,
+        user.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+      );
+    },
+  );
+
+  const pools = [
+    { seed: new BN(randomBytes(8)), mintX, mintY },
+    { seed: new BN(randomBytes(8)), mintX: mintY, mintY: mintZ },
+  ].map((pool) => {
+    const configPda = getConfigPda(pool.seed);
+
+    return {
+      ...pool,
+      configPda,
+      mintLpPda: getMintLpPda(configPda),
+      vaultXPda: getAssociatedTokenAddressSync(
+        pool.mintX.publicKey,
+        configPda,
+        true,
+        TOKEN_PROGRAM_ID,
+      ),
+      vaultYPda: getAssociatedTokenAddressSync(
+        pool.mintY.publicKey,
+        configPda,
+        true,
+        TOKEN_PROGRAM_ID,
+      ),
+    };
+  });
+
+  const remainingAccounts = pools.flatMap((pool) =>
+    [
+      pool.configPda,
+      pool.mintLpPda,
+      pool.vaultXPda,
+      pool.vaultYPda,
+      pool.mintX.publicKey,
+      pool.mintY.publicKey,
+    ].map((pubkey, i) => ({
+      pubkey,
+      isSigner: false,
+      // config tracks dynamic fee state, vaults hold the swapped tokens
+      isWritable: i === 0 || i === 2 || i === 3,
+    })),
+  );
+
+  beforeEach(async () => {
+    const userAtas = [
+      [userAtaXPda, mintX],
+      [userAtaYPda, mintY],
+      [userAtaZPda, mintZ],
+    ].map(([pubkey, mint]: [PublicKey, Keypair]) => {
+      const data = Buffer.alloc(ACCOUNT_SIZE);
+
+      AccountLayout.encode(
+        {
+          amount: 10n,
+          closeAuthority: PublicKey.default,
+          closeAuthorityOption: 0,
+          delegate: PublicKey.default,
+          delegateOption: 0,
+          delegatedAmount: 0n,
+          isNative: 0n,
+          isNativeOption: 0,
+          mint: mint.publicKey,
+          owner: user.publicKey,
+          state: 1,
+        },
+        data,
+      );
+
+      return {
+        pubkey,
+        account: {
+          data,
+          executable: false,
+          lamports: LAMPORTS_PER_SOL,
+          owner: TOKEN_PROGRAM_ID,
+        },
+      };
+    });
+
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...userAtas,
+    ]));
+
+    for (const pool of pools) {
+      await program.methods
+        .initialize({
+          seed: pool.seed,
+          paused: 0,
+          fee: 100,
+          virtualY: new BN(0),
+          flashFee: 0,
+          dynamicFee: false,
+          maxFee: 0,
+        })
+        .accounts({
+          authority: admin.publicKey,
+          mintX: pool.mintX.publicKey,
+          mintY: pool.mintY.publicKey,
+          pair: getPairPda(pool.mintX.publicKey, pool.mintY.publicKey, 100),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+      await program.methods
+        .deposit({
+          amount: new BN(5),
+          maxX: new BN(5),
+          maxY: new BN(5),
+          wrapSol: false,
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: pool.configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    }
+  });
+
+  test("swap through multiple pools", async () => {
+    const initUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+    const initUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+    const initUserAtaZBal = (await getAccount(provider.connection, userAtaZPda))
+      .amount;
+
+    const amount = 2;
+
+    await program.methods
+      .swapRoute({
+        amount: new BN(amount),
+        minOut: new BN(1),
+      })
+      .accounts({
+        user: user.publicKey,
+        mintIn: mintX.publicKey,
+        mintOut: mintZ.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([user])
+      .rpc();
+
+    const postUserAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+    const postUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+    const postUserAtaZBal = (await getAccount(provider.connection, userAtaZPda))
+      .amount;
+
+    expect(Number(initUserAtaXBal - postUserAtaXBal)).toEqual(amount);
+    expect(postUserAtaYBal).toEqual(initUserAtaYBal);
+    expect(Number(postUserAtaZBal)).toBeGreaterThan(Number(initUserAtaZBal));
+  });
+
+  test("throws if route output is below minimum", async () => {
+    try {
+      await program.methods
+        .swapRoute({
+          amount: new BN(2),
+          minOut: new BN(5),
+        })
+        .accounts({
+          user: user.publicKey,
+          mintIn: mintX.publicKey,
+          mintOut: mintZ.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "SlippageExceeded");
+    }
+  });
+
+  test("throws if route does not end in output mint", async () => {
+    try {
+      await program.methods
+        .swapRoute({
+          amount: new BN(2),
+          minOut: new BN(1),
+        })
+        .accounts({
+          user: user.publicKey,
+          mintIn: mintX.publicKey,
+          mintOut: mintZ.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts.slice(0, 6))
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidRoute");
+    }
+  });
+});
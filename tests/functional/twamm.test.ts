@@ -0,0 +1,569 @@
+import { beforeEach, describe, expect, test } from "bun:test";
+import { AutomatedMarketMaker } from "../../target/types/automated_market_maker";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { mintX, mintY } from "../constants";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  getArchivedExpiryPda,
+  getConfigPda,
+  getLockPda,
+  getLongTermOrderPda,
+  getMintLpPda,
+  getPairPda,
+  getTwammPda,
+} from "../pda";
+import {
+  fetchArchivedExpiryAcc,
+  fetchConfigAcc,
+  fetchLongTermOrderAcc,
+  fetchTwammAcc,
+} from "../accounts";
+import { LiteSVM } from "litesvm";
+import { LiteSVMProvider } from "anchor-litesvm";
+import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
+
+describe("twamm", () => {
+  let { litesvm, provider, program } = {} as {
+    litesvm: LiteSVM;
+    provider: LiteSVMProvider;
+    program: Program<AutomatedMarketMaker>;
+  };
+
+  const [admin, user] = Array.from({ length: 2 }, Keypair.generate);
+  const [userAtaXPda, userAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+
+  const seed = new BN(randomBytes(8));
+  const configPda = getConfigPda(seed);
+  const twammPda = getTwammPda(configPda);
+  const [twammAtaXPda, twammAtaYPda] = [mintX, mintY].map((mint) => {
+    return getAssociatedTokenAddressSync(
+      mint.publicKey,
+      twammPda,
+      true,
+      TOKEN_PROGRAM_ID,
+    );
+  });
+  const vaultXPda = getAssociatedTokenAddressSync(
+    mintX.publicKey,
+    configPda,
+    true,
+    TOKEN_PROGRAM_ID,
+  );
+  const vaultYPda = getAssociatedTokenAddressSync(
+    mintY.publicKey,
+    configPda,
+    true,
+    TOKEN_PROGRAM_ID,
+  );
+  const orderId = new BN(1);
+  const orderPda = getLongTermOrderPda(configPda, user.publicKey, orderId);
+
+  const balance = 100_000;
+  const liquidity = 50_000;
+  const saleRate = 1;
+  const interval = 3_600;
+  let endTime: number;
+  let orderAmount: number;
+
+  const twammAccounts = {
+    twamm: twammPda,
+    twammX: twammAtaXPda,
+    twammY: twammAtaYPda,
+  };
+
+  function warp(seconds: number) {
+    const clock = litesvm.getClock();
+    clock.unixTimestamp += BigInt(seconds);
+    litesvm.setClock(clock);
+  }
+
+  beforeEach(async () => {
+    ({ litesvm, provider, program } = await getSetup([
+      ...[admin, user].map((kp) => ({
+        pubkey: kp.publicKey,
+        account: fundedSystemAccountInfo(),
+      })),
+      ...[
+        { mint: mintX.publicKey, pubkey: userAtaXPda },
+        { mint: mintY.publicKey, pubkey: userAtaYPda },
+      ].map(({ mint, pubkey }) => {
+        const data = Buffer.alloc(ACCOUNT_SIZE);
+
+        AccountLayout.encode(
+          {
+            amount: BigInt(balance),
+            closeAuthority: PublicKey.default,
+            closeAuthorityOption: 0,
+            delegate: PublicKey.default,
+            delegateOption: 0,
+            delegatedAmount: 0n,
+            isNative: 0n,
+            isNativeOption: 0,
+            mint,
+            owner: user.publicKey,
+            state: 1,
+          },
+          data,
+        );
+
+        return {
+          pubkey,
+          account: {
+            data,
+            executable: false,
+            lamports: LAMPORTS_PER_SOL,
+            owner: TOKEN_PROGRAM_ID,
+          },
+        };
+      }),
+    ]));
+
+    await program.methods
+      .initialize({
+        seed,
+        paused: 0,
+        fee: 30,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
+      })
+      .accounts({
+        authority: admin.publicKey,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 30),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .deposit({
+        amount: new BN(liquidity),
+        maxX: new BN(liquidity),
+        maxY: new BN(liquidity),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .createTwamm()
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    const now = Number(litesvm.getClock().unixTimestamp);
+    endTime = (Math.floor(now / interval) + 2) * interval;
+    orderAmount = saleRate * (endTime - now);
+
+    await program.methods
+      .placeLongTermOrder({
+        id: orderId,
+        isX: true,
+        saleRate: new BN(saleRate),
+        endTime: new BN(endTime),
+      })
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  async function executeVirtualOrders() {
+    await program.methods
+      .executeVirtualOrders()
+      .accountsPartial({
+        config: configPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  async function archiveExpiry() {
+    await program.methods
+      .archiveTwammExpiry({ endTime: new BN(endTime) })
+      .accountsPartial({
+        payer: admin.publicKey,
+        twamm: twammPda,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function withdrawProceeds(archivedExpiry: PublicKey | null = null) {
+    await program.methods
+      .withdrawLongTermProceeds()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        order: orderPda,
+        archivedExpiry,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  test("place long-term order escrows the full sale", async () => {
+    const configAcc = await fetchConfigAcc(program, configPda);
+    const twammAcc = await fetchTwammAcc(program, twammPda);
+    const orderAcc = await fetchLongTermOrderAcc(program, orderPda);
+
+    expect(configAcc.twamm).toBe(true);
+    expect(twammAcc.saleRateX.toNumber()).toBe(saleRate);
+    expect(twammAcc.expiries.length).toBe(1);
+    expect(twammAcc.expiries[0].endTime.toNumber()).toBe(endTime);
+    expect(orderAcc.endTime.toNumber()).toBe(endTime);
+
+    const twammAtaXBal = (await getAccount(provider.connection, twammAtaXPda))
+      .amount;
+
+    expect(Number(twammAtaXBal)).toBe(orderAmount);
+  });
+
+  test("place long-term order rejects unaligned expiry", async () => {
+    try {
+      await program.methods
+        .placeLongTermOrder({
+          id: new BN(2),
+          isX: true,
+          saleRate: new BN(saleRate),
+          endTime: new BN(endTime + 1),
+        })
+        .accountsPartial({
+          owner: user.publicKey,
+          config: configPda,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOrderExpiry");
+    }
+  });
+
+  test("swap requires twamm accounts", async () => {
+    try {
+      await program.methods
+        .swap({
+          isX: false,
+          amount: new BN(100),
+          min: new BN(1),
+          wrapSol: false,
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingTwammAccounts");
+    }
+  });
+
+  test("swap executes virtual orders first", async () => {
+    warp(600);
+
+    await program.methods
+      .swap({
+        isX: false,
+        amount: new BN(100),
+        min: new BN(1),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...twammAccounts,
+      })
+      .signers([user])
+      .rpc();
+
+    const twammAcc = await fetchTwammAcc(program, twammPda);
+    const twammAtaXBal = (await getAccount(provider.connection, twammAtaXPda))
+      .amount;
+
+    expect(twammAcc.lastExecuted.toNumber()).toBe(
+      Number(litesvm.getClock().unixTimestamp),
+    );
+    expect(Number(twammAtaXBal)).toBe(orderAmount - saleRate * 600);
+  });
+
+  test("fill orders requires twamm accounts", async () => {
+    try {
+      await program.methods
+        .fillOrders()
+        .accountsPartial({
+          cranker: user.publicKey,
+          config: configPda,
+          mintX: mintX.publicKey,
+          mintY: mintY.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingTwammAccounts");
+    }
+  });
+
+  test("flash swap requires twamm accounts", async () => {
+    try {
+      await program.methods
+        .flashSwap({
+          isX: true,
+          amountOut: new BN(1),
+          maxIn: new BN(5),
+          data: Buffer.alloc(0),
+        })
+        .accountsPartial({
+          user: user.publicKey,
+          config: configPda,
+          callbackProgram: TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingTwammAccounts");
+    }
+  });
+
+  test("claim locked fees requires twamm accounts", async () => {
+    const lockId = new BN(1);
+    const now = Number(litesvm.getClock().unixTimestamp);
+
+    await program.methods
+      .lockLp({
+        id: lockId,
+        amount: new BN(1_000),
+        unlockAt: new BN(now + interval),
+        permanent: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    try {
+      await program.methods
+        .claimLockedFees()
+        .accountsPartial({
+          owner: user.publicKey,
+          config: configPda,
+          lock: getLockPda(configPda, user.publicKey, lockId),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingTwammAccounts");
+    }
+  });
+
+  test("swap route rejects pools with a twamm", async () => {
+    try {
+      await program.methods
+        .swapRoute({
+          amount: new BN(100),
+          minOut: new BN(1),
+        })
+        .accounts({
+          user: user.publicKey,
+          mintIn: mintY.publicKey,
+          mintOut: mintX.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          [
+            configPda,
+            getMintLpPda(configPda),
+            vaultXPda,
+            vaultYPda,
+            mintX.publicKey,
+            mintY.publicKey,
+          ].map((pubkey, i) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: i === 0 || i === 2 || i === 3,
+          })),
+        )
+        .signers([user])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "MissingTwammAccounts");
+    }
+  });
+
+  test("execute virtual orders sells into the pool", async () => {
+    const preVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+
+    warp(1_000);
+    await executeVirtualOrders();
+
+    const postVaultXBal = (await getAccount(provider.connection, vaultXPda))
+      .amount;
+    const twammAtaYBal = (await getAccount(provider.connection, twammAtaYPda))
+      .amount;
+
+    expect(Number(postVaultXBal - preVaultXBal)).toBe(saleRate * 1_000);
+    // 1_000 x at ~1:1 less the 0.3% fee
+    expect(Number(twammAtaYBal)).toBeGreaterThan(950);
+    expect(Number(twammAtaYBal)).toBeLessThan(1_000);
+  });
+
+  test("withdraw proceeds pays out and closes finished orders", async () => {
+    warp(1_000);
+
+    const preUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+
+    await withdrawProceeds();
+
+    const midUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+
+    expect(midUserAtaYBal).toBeGreaterThan(preUserAtaYBal);
+    expect(await fetchLongTermOrderAcc(program, orderPda)).not.toBeNull();
+
+    warp(2 * interval);
+    await withdrawProceeds();
+
+    const postUserAtaYBal = (
+      await getAccount(provider.connection, userAtaYPda)
+    ).amount;
+    const twammAcc = await fetchTwammAcc(program, twammPda);
+    const twammAtaXBal = (await getAccount(provider.connection, twammAtaXPda))
+      .amount;
+
+    expect(postUserAtaYBal).toBeGreaterThan(midUserAtaYBal);
+    expect(await fetchLongTermOrderAcc(program, orderPda)).toBeNull();
+    expect(twammAcc.saleRateX.toNumber()).toBe(0);
+    expect(twammAcc.expiries.length).toBe(0);
+    expect(Number(twammAtaXBal)).toBe(0);
+  });
+
+  test("cancel long-term order refunds unsold input", async () => {
+    warp(1_000);
+
+    await program.methods
+      .cancelLongTermOrder()
+      .accountsPartial({
+        owner: user.publicKey,
+        config: configPda,
+        order: orderPda,
+        mintX: mintX.publicKey,
+        mintY: mintY.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userAtaXBal = (await getAccount(provider.connection, userAtaXPda))
+      .amount;
+    const twammAcc = await fetchTwammAcc(program, twammPda);
+
+    expect(Number(userAtaXBal)).toBe(balance - liquidity - saleRate * 1_000);
+    expect(twammAcc.saleRateX.toNumber()).toBe(0);
+    expect(await fetchLongTermOrderAcc(program, orderPda)).toBeNull();
+  });
+
+  test("archive expiry rejects unexecuted expiries", async () => {
+    try {
+      await archiveExpiry();
+    } catch (err) {
+      expectAnchorError(err, "InvalidOrderExpiry");
+    }
+  });
+
+  test("archive expiry frees its slot for new orders", async () => {
+    warp(2 * interval);
+    await executeVirtualOrders();
+    await archiveExpiry();
+
+    const archivedExpiryPda = getArchivedExpiryPda(twammPda, new BN(endTime));
+    const archivedExpiryAcc = await fetchArchivedExpiryAcc(
+      program,
+      archivedExpiryPda,
+    );
+    const twammAcc = await fetchTwammAcc(program, twammPda);
+
+    expect(twammAcc.expiries.length).toBe(0);
+    expect(archivedExpiryAcc.endTime.toNumber()).toBe(endTime);
+    expect(archivedExpiryAcc.earningsPerRateX.toString()).toBe(
+      twammAcc.earningsPerRateX.toString(),
+    );
+  });
+
+  test("withdraw proceeds requires the archived expiry", async () => {
+    warp(2 * interval);
+    await executeVirtualOrders();
+    await archiveExpiry();
+
+    try {
+      await withdrawProceeds();
+    } catch (err) {
+      expectAnchorError(err, "MissingArchivedExpiry");
+    }
+
+    const preUserAtaYBal = (await getAccount(provider.connection, userAtaYPda))
+      .amount;
+
+    await withdrawProceeds(getArchivedExpiryPda(twammPda, new BN(endTime)));
+
+    const postUserAtaYBal = (
+      await getAccount(provider.connection, userAtaYPda)
+    ).amount;
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(postUserAtaYBal).toBeGreaterThan(preUserAtaYBal);
+    expect(await fetchLongTermOrderAcc(program, orderPda)).toBeNull();
+    expect(configAcc.openOrders).toBe(0);
+  });
+});
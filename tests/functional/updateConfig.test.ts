@@ -3,19 +3,20 @@ import { AutomatedMarketMaker } from "../../target/types/automated_market_maker"
 import { BN, Program } from "@coral-xyz/anchor";
 import { Keypair } from "@solana/web3.js";
 import { randomBytes } from "crypto";
-import { mintX, mintY } from "../constants";
+import { mintX, mintY, PAUSE_ALL } from "../constants";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { getConfigPda } from "../pda";
+import { getConfigPda, getPairPda } from "../pda";
 import { fetchConfigAcc } from "../accounts";
 import { LiteSVM } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
 import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
 
 describe("update", () => {
-  let { litesvm, provider, program } = {} as {
+  let { litesvm, provider, program, programAdmin } = {} as {
     litesvm: LiteSVM;
     provider: LiteSVMProvider;
     program: Program<AutomatedMarketMaker>;
+    programAdmin: Keypair;
   };
 
   const [authorityA, authorityB] = Array.from({ length: 2 }, Keypair.generate);
@@ -23,7 +24,7 @@ describe("update", () => {
   const configPda = getConfigPda(seed);
 
   beforeEach(async () => {
-    ({ litesvm, provider, program } = await getSetup(
+    ({ litesvm, provider, program, programAdmin } = await getSetup(
       [authorityA, authorityB].map((kp) => ({
         pubkey: kp.publicKey,
         account: fundedSystemAccountInfo(),
@@ -33,13 +34,18 @@ describe("update", () => {
     await program.methods
       .initialize({
         seed,
-        locked: false,
+        paused: 0,
         fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
       })
       .accounts({
         authority: authorityA.publicKey,
         mintX: mintX.publicKey,
         mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([authorityA])
@@ -47,15 +53,25 @@ describe("update", () => {
   });
 
   test("update a pool config", async () => {
-    const locked = true;
-    const fee = 200;
+    const paused = PAUSE_ALL;
+    const flashFee = 5;
     const authority = authorityB.publicKey;
+    const guardian = authorityA.publicKey;
 
     await program.methods
       .updateConfig({
-        locked,
-        fee,
+        paused,
+        fee: null,
+        flashFee,
         authority,
+        guardian,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
       })
       .accountsPartial({
         authority: authorityA.publicKey,
@@ -66,22 +82,33 @@ describe("update", () => {
 
     const configAcc = await fetchConfigAcc(program, configPda);
 
-    expect(configAcc.locked).toEqual(locked);
-    expect(configAcc.fee).toEqual(fee);
+    expect(configAcc.paused).toEqual(paused);
+    expect(configAcc.fee).toEqual(100);
+    expect(configAcc.flashFee).toEqual(flashFee);
     expect(configAcc.authority).toStrictEqual(authority);
+    expect(configAcc.guardian).toStrictEqual(guardian);
   });
 
   test("throws if signer is not config authority", async () => {
-    const locked = true;
+    const paused = PAUSE_ALL;
     const fee = 200;
     const authority = authorityB.publicKey;
 
     try {
       await program.methods
         .updateConfig({
-          locked,
+          paused,
           fee,
+          flashFee: null,
           authority,
+          guardian: null,
+          maxSwapImpact: null,
+          maxSlotImpact: null,
+          sandwichGuard: null,
+          sandwichFee: null,
+          permissioned: null,
+          allowlistAuthority: null,
+          referralFee: null,
         })
         .accountsPartial({
           authority: authorityB.publicKey,
@@ -93,4 +120,64 @@ describe("update", () => {
       expectAnchorError(err, "InvalidConfigAuthority");
     }
   });
+
+  test("throws if changing the fee tier of a registered pool", async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          paused: null,
+          fee: 200,
+          flashFee: null,
+          authority: null,
+          guardian: null,
+          maxSwapImpact: null,
+          maxSlotImpact: null,
+          sandwichGuard: null,
+          sandwichFee: null,
+          permissioned: null,
+          allowlistAuthority: null,
+          referralFee: null,
+        })
+        .accountsPartial({
+          authority: authorityA.publicKey,
+          config: configPda,
+        })
+        .signers([authorityA])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "FeeTierLocked");
+    }
+  });
+
+  test("set a pool protocol fee", async () => {
+    const protocolFee = 1_000;
+
+    await program.methods
+      .setProtocolFee({ protocolFee })
+      .accountsPartial({
+        admin: programAdmin.publicKey,
+        config: configPda,
+      })
+      .signers([programAdmin])
+      .rpc();
+
+    const configAcc = await fetchConfigAcc(program, configPda);
+
+    expect(configAcc.protocolFee).toEqual(protocolFee);
+  });
+
+  test("throws if pool authority sets the protocol fee", async () => {
+    try {
+      await program.methods
+        .setProtocolFee({ protocolFee: 0 })
+        .accountsPartial({
+          admin: authorityA.publicKey,
+          config: configPda,
+        })
+        .signers([authorityA])
+        .rpc();
+    } catch (err) {
+      expectAnchorError(err, "InvalidProgramAdmin");
+    }
+  });
 });
@@ -3,7 +3,7 @@ import { AutomatedMarketMaker } from "../../target/types/automated_market_maker"
 import { BN, Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { randomBytes } from "crypto";
-import { mintX, mintY } from "../constants";
+import { mintX, mintY, PAUSE_SWAP, PAUSE_WITHDRAW } from "../constants";
 import {
   ACCOUNT_SIZE,
   AccountLayout,
@@ -11,7 +11,7 @@ import {
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { getConfigPda, getMintLpPda } from "../pda";
+import { getConfigPda, getMintLpPda, getPairPda } from "../pda";
 import { LiteSVM } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
 import { expectAnchorError, fundedSystemAccountInfo, getSetup } from "../setup";
@@ -104,13 +104,18 @@ describe("withdraw", () => {
     await program.methods
       .initialize({
         seed,
-        locked: false,
+        paused: 0,
         fee: 100,
+        virtualY: new BN(0),
+        flashFee: 0,
+        dynamicFee: false,
+        maxFee: 0,
       })
       .accounts({
         authority: admin.publicKey,
         mintX: mintX.publicKey,
         mintY: mintY.publicKey,
+        pair: getPairPda(mintX.publicKey, mintY.publicKey, 100),
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([admin])
@@ -124,6 +129,7 @@ describe("withdraw", () => {
         amount: new BN(amount),
         maxX: new BN(amount * (1 + slippage)),
         maxY: new BN(amount * (1 + slippage)),
+        wrapSol: false,
       })
       .accountsPartial({
         user: user.publicKey,
@@ -177,6 +183,7 @@ describe("withdraw", () => {
         amount: new BN(amount),
         minX: new BN(amount * (1 - slippage)),
         minY: new BN(amount * (1 - slippage)),
+        wrapSol: false,
       })
       .accountsPartial({
         user: user.publicKey,
@@ -219,12 +226,119 @@ describe("withdraw", () => {
     expect(Number(postUserAtaLpBal)).toEqual(Number(initUserAtaLpBal) - amount);
   });
 
-  test("throws if withdrawing from a locked pool", async () => {
+  test("withdraw to a different recipient", async () => {
+    const recipient = Keypair.generate();
+    const [recipientAtaXPda, recipientAtaYPda] = [mintX, mintY].map((mint) => {
+      return getAssociatedTokenAddressSync(
+        mint.publicKey,
+        recipient.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+      );
+    });
+
+    const amount = 3;
+    const slippage = 0.01;
+
+    await program.methods
+      .withdraw({
+        amount: new BN(amount),
+        minX: new BN(amount * (1 - slippage)),
+        minY: new BN(amount * (1 - slippage)),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        recipient: recipient.publicKey,
+        recipientX: recipientAtaXPda,
+        recipientY: recipientAtaYPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    for (const ata of [recipientAtaXPda, recipientAtaYPda]) {
+      const { amount: balance } = await getAccount(provider.connection, ata);
+
+      expect(Number(balance)).toBeGreaterThanOrEqual(amount);
+    }
+  });
+
+  test("withdraw from a pool with swaps paused", async () => {
+    await program.methods
+      .updateConfig({
+        paused: PAUSE_SWAP,
+        fee: null,
+        flashFee: null,
+        authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
+      })
+      .accountsPartial({
+        authority: admin.publicKey,
+        config: configPda,
+      })
+      .signers([admin])
+      .rpc();
+
+    const mintLp = getMintLpPda(configPda);
+    const userAtaLpPda = getAssociatedTokenAddressSync(
+      mintLp,
+      user.publicKey,
+      false,
+      TOKEN_PROGRAM_ID,
+    );
+    const initUserAtaLpBal = (
+      await getAccount(provider.connection, userAtaLpPda)
+    ).amount;
+
+    const amount = 3;
+    const slippage = 0.01;
+
+    await program.methods
+      .withdraw({
+        amount: new BN(amount),
+        minX: new BN(amount * (1 - slippage)),
+        minY: new BN(amount * (1 - slippage)),
+        wrapSol: false,
+      })
+      .accountsPartial({
+        user: user.publicKey,
+        config: configPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const postUserAtaLpBal = (
+      await getAccount(provider.connection, userAtaLpPda)
+    ).amount;
+
+    expect(Number(postUserAtaLpBal)).toEqual(Number(initUserAtaLpBal) - amount);
+  });
+
+  test("throws if withdrawing from a pool with withdrawals paused", async () => {
     await program.methods
       .updateConfig({
-        locked: true,
+        paused: PAUSE_WITHDRAW,
         fee: null,
+        flashFee: null,
         authority: null,
+        guardian: null,
+        maxSwapImpact: null,
+        maxSlotImpact: null,
+        sandwichGuard: null,
+        sandwichFee: null,
+        permissioned: null,
+        allowlistAuthority: null,
+        referralFee: null,
       })
       .accountsPartial({
         authority: admin.publicKey,
@@ -242,6 +356,7 @@ describe("withdraw", () => {
           amount: new BN(amount),
           minX: new BN(amount * (1 - slippage)),
           minY: new BN(amount * (1 - slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
@@ -251,7 +366,7 @@ describe("withdraw", () => {
         .signers([user])
         .rpc();
     } catch (err) {
-      expectAnchorError(err, "PoolLocked");
+      expectAnchorError(err, "OperationPaused");
     }
   });
 
@@ -265,6 +380,7 @@ describe("withdraw", () => {
           amount: new BN(amount),
           minX: new BN(amount * (1 - slippage)),
           minY: new BN(amount * (1 - slippage)),
+          wrapSol: false,
         })
         .accountsPartial({
           user: user.publicKey,
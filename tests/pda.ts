@@ -3,6 +3,9 @@ import { BN } from "@coral-xyz/anchor";
 import idl from "../target/idl/automated_market_maker.json";
 
 const AMM_PROGRAM_ID = new PublicKey(idl.address);
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111",
+);
 
 export function getConfigPda(seed: BN) {
   return PublicKey.findProgramAddressSync(
@@ -17,3 +20,133 @@ export function getMintLpPda(configPda: PublicKey) {
     AMM_PROGRAM_ID,
   )[0];
 }
+
+export function getFactoryPda() {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("factory")],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getPairPda(mintX: PublicKey, mintY: PublicKey, fee: number) {
+  const feeBuffer = Buffer.alloc(2);
+  feeBuffer.writeUInt16LE(fee);
+
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pair"),
+      ...[mintX, mintY]
+        .map((mint) => mint.toBuffer())
+        .sort(Buffer.compare),
+      feeBuffer,
+    ],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getProgramConfigPda() {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getPositionPda(configPda: PublicKey, owner: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position"), configPda.toBuffer(), owner.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getAllowlistPda(configPda: PublicKey, user: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("allowlist"), configPda.toBuffer(), user.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getSwapRecordPda(configPda: PublicKey, owner: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("swap_record"), configPda.toBuffer(), owner.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getFarmPda(configPda: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("farm"), configPda.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getStakePda(farmPda: PublicKey, owner: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stake"), farmPda.toBuffer(), owner.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getLockPda(configPda: PublicKey, owner: PublicKey, id: BN) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("lock"),
+      configPda.toBuffer(),
+      owner.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getOrderPda(configPda: PublicKey, owner: PublicKey, id: BN) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("order"),
+      configPda.toBuffer(),
+      owner.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getTwammPda(configPda: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("twamm"), configPda.toBuffer()],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getLongTermOrderPda(
+  configPda: PublicKey,
+  owner: PublicKey,
+  id: BN,
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("long_term_order"),
+      configPda.toBuffer(),
+      owner.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getArchivedExpiryPda(twammPda: PublicKey, endTime: BN) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("twamm_expiry"),
+      twammPda.toBuffer(),
+      endTime.toArrayLike(Buffer, "le", 8),
+    ],
+    AMM_PROGRAM_ID,
+  )[0];
+}
+
+export function getProgramDataPda() {
+  return PublicKey.findProgramAddressSync(
+    [AMM_PROGRAM_ID.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID,
+  )[0];
+}
@@ -2,9 +2,16 @@ import { AnchorError, Program } from "@coral-xyz/anchor";
 import { AutomatedMarketMaker } from "../target/types/automated_market_maker";
 import idl from "../target/idl/automated_market_maker.json";
 import { MINT_SIZE, MintLayout, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
-import { mintX, mintY } from "./constants";
-import { AccountInfoBytes } from "litesvm";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { FEE_TIERS, mintX, mintY, mintZ } from "./constants";
+import { BPF_LOADER_UPGRADEABLE_ID, getProgramDataPda } from "./pda";
+import { readFileSync } from "fs";
+import { AccountInfoBytes, LiteSVM } from "litesvm";
 import { fromWorkspace, LiteSVMProvider } from "anchor-litesvm";
 import { expect } from "bun:test";
 
@@ -13,11 +20,11 @@ export async function getSetup(
 ) {
   const litesvm = fromWorkspace("./");
 
-  const [mintXData, mintYData] = Array.from({ length: 2 }, () =>
+  const [mintXData, mintYData, mintZData] = Array.from({ length: 3 }, () =>
     Buffer.alloc(MINT_SIZE),
   );
 
-  [mintXData, mintYData].forEach((data) => {
+  [mintXData, mintYData, mintZData].forEach((data) => {
     MintLayout.encode(
       {
         decimals: 6,
@@ -50,6 +57,7 @@ export async function getSetup(
   const mintMap = new Map<PublicKey, Buffer>([
     [mintX.publicKey, mintXData],
     [mintY.publicKey, mintYData],
+    [mintZ.publicKey, mintZData],
   ]);
 
   for (const [pubkey, data] of mintMap.entries()) {
@@ -73,7 +81,69 @@ export async function getSetup(
   const provider = new LiteSVMProvider(litesvm);
   const program = new Program<AutomatedMarketMaker>(idl, provider);
 
-  return { litesvm, provider, program };
+  const programAdmin = Keypair.generate();
+  litesvm.airdrop(programAdmin.publicKey, BigInt(LAMPORTS_PER_SOL));
+  deployUpgradeable(litesvm, program.programId, programAdmin.publicKey);
+
+  await program.methods
+    .initializeFactory()
+    .accounts({
+      payer: programAdmin.publicKey,
+    })
+    .signers([programAdmin])
+    .rpc();
+
+  await program.methods
+    .initializeProgramConfig({
+      protocolFee: 0,
+      feeTiers: FEE_TIERS,
+    })
+    .accounts({
+      admin: programAdmin.publicKey,
+      programData: getProgramDataPda(),
+    })
+    .signers([programAdmin])
+    .rpc();
+
+  return { litesvm, provider, program, programAdmin };
+}
+
+// Redeploys the program under the upgradeable loader so that
+// `initialize_program_config` can check its upgrade authority.
+function deployUpgradeable(
+  litesvm: LiteSVM,
+  programId: PublicKey,
+  upgradeAuthority: PublicKey,
+) {
+  const elf = readFileSync("target/deploy/automated_market_maker.so");
+  const programDataPda = getProgramDataPda();
+
+  // UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }
+  const programData = Buffer.alloc(45 + elf.length);
+  programData.writeUInt32LE(3, 0);
+  programData.writeUInt8(1, 12);
+  upgradeAuthority.toBuffer().copy(programData, 13);
+  elf.copy(programData, 45);
+
+  // UpgradeableLoaderState::Program { programdata_address }
+  const programAccount = Buffer.alloc(36);
+  programAccount.writeUInt32LE(2, 0);
+  programDataPda.toBuffer().copy(programAccount, 4);
+
+  litesvm.setAccount(programDataPda, {
+    data: programData,
+    executable: false,
+    lamports: Number(
+      litesvm.minimumBalanceForRentExemption(BigInt(programData.length)),
+    ),
+    owner: BPF_LOADER_UPGRADEABLE_ID,
+  });
+  litesvm.setAccount(programId, {
+    data: programAccount,
+    executable: true,
+    lamports: LAMPORTS_PER_SOL,
+    owner: BPF_LOADER_UPGRADEABLE_ID,
+  });
 }
 
 export function fundedSystemAccountInfo(